@@ -0,0 +1,87 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicIsize, Ordering},
+    },
+};
+
+/// System allocator that can keep track of the heap usage while a part is
+/// measured, so the report can show how much memory each part needed.
+/// Outside of [`measure_peak`] it only checks a flag and passes through.
+pub struct TrackingAllocator;
+
+static TRACKING: AtomicBool = AtomicBool::new(false);
+/// Bytes allocated since tracking started, negative if more were freed
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+/// Only one measurement at a time, as they share the counters
+static MEASURING: Mutex<()> = Mutex::new(());
+
+fn track(change: isize) {
+    if TRACKING.load(Ordering::Relaxed) {
+        let current = CURRENT.fetch_add(change, Ordering::Relaxed) + change;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            track(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        track(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            track(new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f` and returns its result along with the peak number of bytes
+/// allocated on top of what was live before. Allocations from other threads
+/// running at the same time are counted too.
+pub fn measure_peak<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let _guard = MEASURING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    CURRENT.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+    TRACKING.store(true, Ordering::Relaxed);
+    let result = f();
+    TRACKING.store(false, Ordering::Relaxed);
+    (result, PEAK.load(Ordering::Relaxed) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_peak() {
+        let (length, peak) = measure_peak(|| vec![0u8; 1 << 20].len());
+        assert_eq!(length, 1 << 20);
+        assert!(peak >= 1 << 20);
+    }
+
+    #[test]
+    fn test_nothing_is_tracked_outside_measurements() {
+        let _guard = MEASURING
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let peak = PEAK.load(Ordering::Relaxed);
+        std::hint::black_box(vec![0u8; 1 << 20]);
+        assert!(!TRACKING.load(Ordering::Relaxed));
+        assert_eq!(PEAK.load(Ordering::Relaxed), peak);
+    }
+}
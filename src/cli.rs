@@ -1,7 +1,103 @@
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::utils::viz::Format;
 
 #[derive(Parser)]
+#[command(subcommand_negates_reqs = true)]
 pub struct Cli {
-    #[arg(short, long)]
-    pub day: u8,
+    #[arg(short, long, required = true)]
+    pub day: Option<u8>,
+
+    /// Also render the day's visualization, if it has one
+    #[arg(long, value_enum)]
+    pub visualize: Option<VisualizeFormat>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Output format of the `--visualize` flag
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum VisualizeFormat {
+    /// Animate the frames in the terminal
+    Ansi,
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl From<VisualizeFormat> for Format {
+    fn from(format: VisualizeFormat) -> Self {
+        match format {
+            VisualizeFormat::Ansi => Format::Ansi,
+            VisualizeFormat::Ppm => Format::Ppm,
+            VisualizeFormat::Png => Format::Png,
+            VisualizeFormat::Svg => Format::Svg,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Generate a random, syntactically valid input for a day
+    Gen {
+        #[arg(short, long)]
+        day: u8,
+        /// Number of elements to generate (lines, points, ranges, grid side...)
+        #[arg(short, long, default_value_t = 1000)]
+        size: usize,
+        /// Seed for reproducible inputs, defaults to the current time
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Write the input to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Explore a day's input interactively
+    Repl {
+        #[arg(short, long)]
+        day: u8,
+        /// Start on the sample instead of the puzzle input
+        #[arg(long)]
+        sample: bool,
+    },
+    /// Serve the solutions over HTTP on localhost
+    Serve {
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+    },
+    /// Run every day and write an HTML and Markdown report
+    Report {
+        /// Directory to write report.html and report.md into
+        #[arg(short, long, default_value = "report")]
+        output: PathBuf,
+        /// Run on the samples instead of the puzzle inputs
+        #[arg(long)]
+        sample: bool,
+        /// Run each day in its own child process so crashes don't stop the report
+        #[arg(long)]
+        sandbox: bool,
+        /// CPU time limit per sandboxed day, in seconds
+        #[arg(long, default_value_t = 60)]
+        cpu_seconds: u64,
+        /// Address space limit per sandboxed day, in MiB
+        #[arg(long, default_value_t = 4096)]
+        memory_mb: u64,
+        /// Wall-clock time limit per sandboxed day, in seconds
+        #[arg(long, default_value_t = 120)]
+        wall_seconds: u64,
+    },
+    /// Runs a single day for `report --sandbox`, not meant to be called directly
+    #[command(hide = true)]
+    SandboxChild {
+        #[arg(short, long)]
+        day: u8,
+        #[arg(short, long)]
+        input: PathBuf,
+        /// Input for part 2, defaults to `--input`
+        #[arg(long)]
+        part2_input: Option<PathBuf>,
+    },
 }
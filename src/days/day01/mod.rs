@@ -1,11 +1,21 @@
 use std::str::FromStr;
 
-use crate::solution::Solution;
+use crate::{
+    generator::Generator,
+    repl::Inspect,
+    solution::Solution,
+    utils::{
+        direction::Direction,
+        parse::{ParseError, parse_at, parse_lines},
+        rng::Rng,
+    },
+};
 use anyhow::Result;
+use itertools::Itertools;
 
 pub struct Day01;
 
-struct Safe {
+pub struct Safe {
     position: u8,
     instructions: Vec<i16>,
 }
@@ -77,30 +87,28 @@ impl Safe {
 }
 
 impl FromStr for Safe {
-    type Err = anyhow::Error;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let position = 50;
-        let instructions = s
-            .lines()
-            .map(|line| {
-                let mut chars = line.chars();
-                let direction_letter = chars
-                    .next()
-                    .ok_or(anyhow::anyhow!("Invalid direction letter"))?;
-                let steps = chars.as_str().parse::<i16>()?;
-                let direction = match direction_letter {
-                    'L' => -1,
-                    'R' => 1,
-                    _ => {
-                        return Err(anyhow::anyhow!(
-                            "Invalid direction letter: {}",
-                            direction_letter
-                        ));
-                    }
-                };
-                Ok(steps * direction)
-            })
-            .collect::<Result<Vec<_>>>()?;
+        let instructions = parse_lines(s, |line| {
+            let direction_letter = line
+                .chars()
+                .next()
+                .ok_or_else(|| ParseError::at(line, line, "Missing direction letter"))?;
+            let (letter, steps) = line.split_at(direction_letter.len_utf8());
+            let direction = match direction_letter {
+                'L' => Direction::Left,
+                'R' => Direction::Right,
+                _ => {
+                    return Err(ParseError::at(
+                        line,
+                        letter,
+                        format!("Invalid direction letter: {}", direction_letter),
+                    ));
+                }
+            };
+            Ok(parse_at::<i16>(line, steps)? * direction.to_vec2d().x() as i16)
+        })?;
         Ok(Self::new(position, instructions))
     }
 }
@@ -111,14 +119,61 @@ impl Solution for Day01 {
     }
 
     fn part1(input: &str) -> Result<Self::Answer> {
-        let mut safe = Safe::from_str(input)?;
-        Ok(safe.count_zeros() as u32)
+        Self::part1_parsed(&Self::parse(input)?)
     }
 
     fn part2(input: &str) -> Result<Self::Answer> {
-        let mut safe = Safe::from_str(input)?;
+        Self::part2_parsed(&Self::parse(input)?)
+    }
+}
+
+/// Generates `size` rotations of 1 to 999 clicks in a random direction.
+impl Generator for Day01 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| format!("{}{}", rng.choose(&['L', 'R']), rng.range(1..1000)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Inspect for Day01 {
+    type Parsed = Safe;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
+    }
+
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        let mut safe = Safe::new(parsed.position, parsed.instructions.clone());
+        Ok(safe.count_zeros() as u32)
+    }
+
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        let mut safe = Safe::new(parsed.position, parsed.instructions.clone());
         Ok(safe.count_zeros_every_click() as u32)
     }
+
+    fn structures() -> &'static [&'static str] {
+        &["instructions", "positions"]
+    }
+
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String> {
+        let dump = match structure {
+            "instructions" => parsed.instructions.iter().join("\n"),
+            "positions" => {
+                let mut safe = Safe::new(parsed.position, parsed.instructions.clone());
+                let mut positions = vec![safe.position];
+                for instruction in &parsed.instructions {
+                    safe.position = safe.next_position(*instruction);
+                    positions.push(safe.position);
+                }
+                positions.iter().join("\n")
+            }
+            _ => return None,
+        };
+        Some(dump)
+    }
 }
 
 #[cfg(test)]
@@ -377,4 +432,24 @@ mod tests {
         // So: 1 (rotation) + 0 (doesn't land at 0) = 1
         assert_eq!(safe.count_zero_crossings(150), 1);
     }
+
+    #[test]
+    fn test_only_l_and_r_are_directions() {
+        assert_eq!("L5\nR7".parse::<Safe>().unwrap().instructions, vec![-5, 7]);
+        for letter in ['<', '>', 'E', 'W', 'U', 'x'] {
+            let err = format!("R1\n{}5", letter).parse::<Safe>().err().unwrap();
+            assert_eq!(
+                err.message(),
+                format!("Invalid direction letter: {}", letter)
+            );
+            assert_eq!((err.line, err.column), (2, 1));
+        }
+    }
+
+    #[test]
+    fn test_generated_input() {
+        let input = Day01.generate(200, &mut Rng::new(2025));
+        assert!(Day01::part1(&input).is_ok());
+        assert!(Day01::part2(&input).is_ok());
+    }
 }
@@ -1,100 +1,103 @@
 use rayon::prelude::*;
-use std::{ops::RangeInclusive, str::FromStr};
-
-use crate::solution::Solution;
+use std::str::FromStr;
+
+use crate::{
+    generator::Generator,
+    repl::Inspect,
+    solution::Solution,
+    utils::{
+        interval_set::IntervalSet,
+        math::{digit_count, is_repeated_block},
+        parse::{ParseError, parse_range, separated_by},
+        rng::Rng,
+    },
+};
 use anyhow::Result;
 
 pub struct Day02;
 
-struct Ranges(Vec<IDRange>);
+impl Inspect for Day02 {
+    type Parsed = Ranges;
 
-impl Ranges {
-    fn find_invalid_ids(&self) -> Vec<u64> {
-        self.0
-            .iter()
-            .flat_map(|range| range.find_invalid_ids())
-            .collect()
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
     }
 
-    fn find_invalid_ids_part2(&self) -> Vec<u64> {
-        self.0
-            .par_iter()
-            .flat_map(|range| range.find_invalid_ids_part2())
-            .collect()
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        Ok(parsed.find_invalid_ids().iter().sum())
     }
-}
 
-impl FromStr for Ranges {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let ranges = s
-            .split(',')
-            .map(|range| IDRange::from_str(range))
-            .collect::<Result<Vec<IDRange>, anyhow::Error>>()?;
-        Ok(Self(ranges))
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        Ok(parsed.find_invalid_ids_part2().iter().sum())
     }
-}
 
-struct IDRange(RangeInclusive<u64>);
+    fn structures() -> &'static [&'static str] {
+        &["ranges", "invalid"]
+    }
 
-impl IDRange {
-    fn new(start: u64, end: u64) -> Self {
-        Self(start..=end)
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String> {
+        let dump = match structure {
+            "ranges" => parsed
+                .0
+                .iter()
+                .map(|range| {
+                    let (start, end) = (range.start(), range.end());
+                    format!("{}-{} ({} IDs)", start, end, end - start + 1)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "invalid" => parsed
+                .0
+                .iter()
+                .map(|range| {
+                    let invalid = range
+                        .clone()
+                        .filter(|&id| !valid_id(id))
+                        .collect::<Vec<_>>();
+                    format!("{:?}: {:?}", range, invalid)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => return None,
+        };
+        Some(dump)
     }
+}
+
+/// The ID ranges to check, merged so that no ID is checked twice
+pub struct Ranges(IntervalSet);
 
+impl Ranges {
     fn find_invalid_ids(&self) -> Vec<u64> {
-        self.0.clone().filter(|&id| !Self::valid_id(id)).collect()
+        self.0.values().filter(|&id| !valid_id(id)).collect()
     }
 
     fn find_invalid_ids_part2(&self) -> Vec<u64> {
         self.0
-            .clone()
+            .iter()
+            .collect::<Vec<_>>()
             .into_par_iter()
-            .filter(|&id| Self::is_invalid_id_part2(id))
+            .flat_map(|range| range.into_par_iter().filter(|&id| is_invalid_id_part2(id)))
             .collect()
     }
+}
 
-    fn valid_id(id: u64) -> bool {
-        let id_str = id.to_string();
-        if id_str.len() % 2 != 0 {
-            return true;
-        }
-        let (first, second) = id_str.split_at(id_str.len() / 2);
-
-        first != second
-    }
-
-    fn is_invalid_id_part2(id: u64) -> bool {
-        let id_str = id.to_string();
-        let len = id_str.len();
-
-        for sub_len in 1..=len / 2 {
-            let substring = &id_str[..sub_len];
-            // Check if the ID length is divisible by the substring length
-            if len % sub_len == 0 {
-                // Check if repeating the substring creates the full ID
-                if substring.repeat(len / sub_len) == id_str {
-                    return true;
-                }
-            }
-        }
+impl FromStr for Ranges {
+    type Err = ParseError;
 
-        false
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ranges = separated_by(s, ',', parse_range)?;
+        Ok(Self(ranges.into_iter().collect()))
     }
 }
 
-impl FromStr for IDRange {
-    type Err = anyhow::Error;
+fn valid_id(id: u64) -> bool {
+    let len = digit_count(id);
+    !(len.is_multiple_of(2) && is_repeated_block(id, len / 2))
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start, end) = s
-            .split_once('-')
-            .ok_or_else(|| anyhow::anyhow!("Invalid range"))?;
-        let start = start.parse::<u64>()?;
-        let end = end.parse::<u64>()?;
-        Ok(Self::new(start, end))
-    }
+fn is_invalid_id_part2(id: u64) -> bool {
+    (1..=digit_count(id) / 2).any(|block_len| is_repeated_block(id, block_len))
 }
 
 impl Solution for Day02 {
@@ -105,15 +108,27 @@ impl Solution for Day02 {
     }
 
     fn part1(input: &str) -> Result<Self::Answer> {
-        let ranges = Ranges::from_str(input)?;
-        let invalid_ids = ranges.find_invalid_ids();
-        Ok(invalid_ids.iter().sum())
+        Self::part1_parsed(&Self::parse(input)?)
     }
 
     fn part2(input: &str) -> Result<Self::Answer> {
-        let ranges = Ranges::from_str(input)?;
-        let invalid_ids = ranges.find_invalid_ids_part2();
-        Ok(invalid_ids.iter().sum())
+        Self::part2_parsed(&Self::parse(input)?)
+    }
+}
+
+/// Generates `size` disjoint ID ranges, each spanning at most a few thousand IDs
+/// so that the brute force part 2 stays tractable.
+impl Generator for Day02 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut start = rng.range(1..100);
+        let mut ranges = Vec::with_capacity(size);
+        for _ in 0..size {
+            let end = start + rng.range(0..5000);
+            ranges.push(format!("{}-{}", start, end));
+            start = end + rng.range(1..1_000_000_000_000 / size.max(1) as i64);
+        }
+        rng.shuffle(&mut ranges);
+        ranges.join(",")
     }
 }
 
@@ -132,4 +147,11 @@ mod tests {
         let answer = Day02.run_test2();
         assert_eq!(answer, 4174379265);
     }
+
+    #[test]
+    fn test_generated_input() {
+        let input = Day02.generate(50, &mut Rng::new(2025));
+        assert!(Day02::part1(&input).is_ok());
+        assert!(Day02::part2(&input).is_ok());
+    }
 }
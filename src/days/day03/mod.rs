@@ -1,10 +1,22 @@
-use std::fmt::Display;
-
-use crate::solution::Solution;
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    generator::Generator,
+    repl::Inspect,
+    solution::Solution,
+    utils::{
+        math::from_digits,
+        parse::{ParseError, chars, parse_lines},
+        rng::Rng,
+    },
+};
 use anyhow::Result;
 
 pub struct Day03;
 
+/// Batteries turned on in each bank for part 2, the most any part needs
+const PART2_BATTERIES: usize = 12;
+
 impl Solution for Day03 {
     type Answer = u64;
 
@@ -13,38 +25,73 @@ impl Solution for Day03 {
     }
 
     fn part1(input: &str) -> Result<Self::Answer> {
-        let banks = input
-            .lines()
-            .map(|line| BatteryBank::new(line))
-            .collect::<Vec<_>>();
+        Self::part1_parsed(&Self::parse(input)?)
+    }
+
+    fn part2(input: &str) -> Result<Self::Answer> {
+        Self::part2_parsed(&Self::parse(input)?)
+    }
+}
+
+impl Inspect for Day03 {
+    type Parsed = Vec<BatteryBank>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(parse_lines(input, BatteryBank::from_str)?)
+    }
+
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
         let mut joltage = 0;
-        for mut bank in banks {
+        for mut bank in parsed.iter().cloned() {
             joltage += bank.find_highest_joltage(2);
         }
-        Ok(joltage as u64)
+        Ok(joltage)
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let banks = input
-            .lines()
-            .map(|line| BatteryBank::new(line))
-            .collect::<Vec<_>>();
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
         let mut joltage = 0;
-        for mut bank in banks {
-            joltage += bank.find_highest_joltage(12);
+        for mut bank in parsed.iter().cloned() {
+            joltage += bank.find_highest_joltage(PART2_BATTERIES);
         }
-        Ok(joltage as u64)
+        Ok(joltage)
+    }
+
+    fn structures() -> &'static [&'static str] {
+        &["banks", "joltages"]
+    }
+
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String> {
+        let dump = match structure {
+            "banks" => parsed
+                .iter()
+                .map(|bank| bank.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "joltages" => parsed
+                .iter()
+                .map(|bank| {
+                    let part1 = bank.clone().find_highest_joltage(2);
+                    let part2 = bank.clone().find_highest_joltage(PART2_BATTERIES);
+                    format!("{}: {} / {}", bank, part1, part2)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => return None,
+        };
+        Some(dump)
     }
 }
 
-struct BatteryBank(Vec<char>);
+/// The joltage rating of each battery, one digit each
+#[derive(Clone)]
+pub struct BatteryBank(Vec<u8>);
 
 impl BatteryBank {
     fn new(input: &str) -> Self {
-        Self(input.chars().collect())
+        Self(input.bytes().map(|b| b - b'0').collect())
     }
 
-    fn remove_highest_available_bettery(&mut self, batteries_remaining: usize) -> char {
+    fn remove_highest_available_bettery(&mut self, batteries_remaining: usize) -> u8 {
         let battery_pool = &self.0[..=self.0.len() - batteries_remaining];
         let highest_battery = *battery_pool.iter().max_by_key(|c| **c).unwrap();
         let highest_battery_index = battery_pool
@@ -56,26 +103,54 @@ impl BatteryBank {
         highest_battery
     }
 
-    fn find_highest_joltage(&mut self, length: usize) -> usize {
+    fn find_highest_joltage(&mut self, length: usize) -> u64 {
         let mut joltage = vec![];
         let mut remaining_batteries = length;
-        // println!("Battery bank: {}", self);
         while remaining_batteries > 0 {
             joltage.push(self.remove_highest_available_bettery(remaining_batteries));
             remaining_batteries -= 1;
         }
-        // println!("Joltage: {:?}", joltage);
-        joltage
-            .into_iter()
-            .collect::<String>()
-            .parse::<usize>()
-            .unwrap()
+        from_digits(joltage)
+    }
+}
+
+impl FromStr for BatteryBank {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        chars(s, |c| c.is_ascii_digit().then_some(c))?;
+        if s.len() < PART2_BATTERIES {
+            return Err(ParseError::at(
+                s,
+                s,
+                format!(
+                    "Expected at least {} batteries, found {}",
+                    PART2_BATTERIES,
+                    s.len()
+                ),
+            ));
+        }
+        Ok(Self::new(s))
     }
 }
 
 impl Display for BatteryBank {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0.iter().collect::<String>())
+        self.0.iter().try_for_each(|digit| write!(f, "{}", digit))
+    }
+}
+
+/// Generates `size` battery banks of 100 batteries each, rated 1 to 9.
+impl Generator for Day03 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                (0..100)
+                    .map(|_| char::from(b'0' + rng.range(1..=9) as u8))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
 
@@ -124,4 +199,22 @@ mod tests {
         let mut bank = BatteryBank::new("999999999999998");
         assert_eq!(bank.find_highest_joltage(2), 99);
     }
+
+    #[test]
+    fn test_short_bank_is_rejected() {
+        let err = Day03::part1("987654321111111\n98765").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Expected at least 12 batteries, found 5")
+        );
+        assert!(err.to_string().contains("line 2"));
+        assert!(Day03::part1("987654321111111\n").is_ok());
+    }
+
+    #[test]
+    fn test_generated_input() {
+        let input = Day03.generate(50, &mut Rng::new(2025));
+        assert!(Day03::part1(&input).is_ok());
+        assert!(Day03::part2(&input).is_ok());
+    }
 }
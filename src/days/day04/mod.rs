@@ -1,9 +1,17 @@
-use std::collections::HashSet;
-use std::convert::Infallible;
 use std::str::FromStr;
 
+use crate::generator::Generator;
+use crate::repl::Inspect;
 use crate::solution::Solution;
-use crate::utils::vec2d::Vec2D;
+use crate::utils::{
+    bit_grid::BitGrid,
+    grid::Grid,
+    parse::ParseError,
+    rng::Rng,
+    simulation::Simulation,
+    vec2d::{self, Vec2D},
+    viz::{Color, Scene, Style},
+};
 use anyhow::Result;
 
 pub struct Day04;
@@ -16,80 +24,155 @@ impl Solution for Day04 {
     }
 
     fn part1(input: &str) -> Result<Self::Answer> {
+        Self::part1_parsed(&Self::parse(input)?)
+    }
+
+    fn part2(input: &str) -> Result<Self::Answer> {
+        Self::part2_parsed(&Self::parse(input)?)
+    }
+
+    /// One frame per removal round, highlighting the rolls about to be removed.
+    fn visualize(input: &str) -> Result<Vec<Scene>> {
         let grid = PaperGrid::from_str(input)?;
+        let bounds = Vec2D::new(grid.0.width() as i64 - 1, grid.0.height() as i64 - 1);
+        let mut frames = Vec::new();
+        Simulation::new(grid, PaperGrid::without_accessible_rolls)
+            .with_hook(|_, grid| {
+                let mut frame = Scene::new().with_bounds(vec2d::ZERO, bounds);
+                frame.draw(
+                    &grid.rolls().collect::<Vec<_>>(),
+                    Style::new('@', Color::WHITE),
+                );
+                frame.draw(
+                    &grid.accessible_rolls().ones().collect::<Vec<_>>(),
+                    Style::new('x', Color::RED),
+                );
+                frames.push(frame);
+            })
+            .run_to_fixpoint();
+        Ok(frames)
+    }
+}
 
-        Ok(grid
-            .0
-            .iter()
-            .filter(|p| grid.accessible_by_forklift(p))
-            .count() as u64)
+impl Inspect for Day04 {
+    type Parsed = PaperGrid;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let mut grid = PaperGrid::from_str(input)?;
-        let size = grid.size();
-        grid.remove_all_accessible_rolls();
-        Ok((size - grid.size()) as u64)
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        Ok(parsed.accessible_rolls().count_ones() as u64)
+    }
+
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        Ok(parsed.clone().remove_all_accessible_rolls() as u64)
+    }
+
+    fn structures() -> &'static [&'static str] {
+        &["grid", "accessible", "remaining"]
+    }
+
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String> {
+        let dump = match structure {
+            "grid" => parsed.render(|_| '@'),
+            "accessible" => parsed.render(|p| {
+                if parsed.accessible_by_forklift(p) {
+                    'x'
+                } else {
+                    '@'
+                }
+            }),
+            "remaining" => {
+                let mut grid = PaperGrid(parsed.0.clone());
+                grid.remove_all_accessible_rolls();
+                grid.render(|_| '@')
+            }
+            _ => return None,
+        };
+        Some(dump)
     }
 }
 
-struct PaperGrid(HashSet<Vec2D>);
+/// The warehouse floor, with a set bit wherever there is a roll of paper
+#[derive(Clone, PartialEq, Eq)]
+pub struct PaperGrid(BitGrid);
 
 impl PaperGrid {
     fn accessible_by_forklift(&self, position: &Vec2D) -> bool {
-        let adjacent_rolls = position
-            .adjacent_8()
-            .into_iter()
-            .filter(|p| self.0.contains(p));
+        self.0.count_neighbours_8(position) < 4
+    }
 
-        adjacent_rolls.count() < 4
+    fn rolls(&self) -> impl Iterator<Item = Vec2D> + '_ {
+        self.0.ones()
     }
 
-    fn size(&self) -> usize {
-        self.0.len()
+    /// Draws the rolls, using `cell` to pick the character of each roll
+    fn render(&self, cell: impl Fn(&Vec2D) -> char) -> String {
+        Grid::from(&self.0)
+            .map(|position, &roll| if roll { cell(&position) } else { '.' })
+            .to_string()
     }
 
-    fn remove_accessible_rolls(&mut self) {
-        let accessible_rolls = self
-            .0
-            .iter()
-            .filter(|p| self.accessible_by_forklift(p))
-            .copied()
-            .collect::<HashSet<Vec2D>>();
+    /// The rolls with fewer than 4 neighbouring rolls, found a row word at a time
+    fn accessible_rolls(&self) -> BitGrid {
+        let mut accessible = self.0.neighbour_count_mask(|count| count < 4);
+        accessible.intersect_with(&self.0);
+        accessible
+    }
 
-        self.0 = self.0.difference(&accessible_rolls).copied().collect();
+    /// The floor after one round of removing every accessible roll
+    fn without_accessible_rolls(&self) -> Self {
+        let mut remaining = self.0.clone();
+        remaining.subtract(&self.accessible_rolls());
+        Self(remaining)
     }
 
-    fn remove_all_accessible_rolls(&mut self) {
-        let mut current_size = self.size();
-        loop {
-            self.remove_accessible_rolls();
-            if self.size() == current_size {
-                break;
-            }
-            current_size = self.size();
-        }
+    /// Removes accessible rolls round after round until none are left,
+    /// returning how many were removed
+    fn remove_all_accessible_rolls(&mut self) -> usize {
+        let before = self.0.count_ones();
+        let mut simulation = Simulation::new(self.clone(), Self::without_accessible_rolls)
+            .with_hook(|round, grid| {
+                log::debug!("After {} rounds: {} rolls left", round, grid.0.count_ones())
+            });
+        let outcome = simulation.run_to_fixpoint();
+        log::debug!("Removing rolls settled: {:?}", outcome);
+        *self = simulation.into_state();
+        before - self.0.count_ones()
     }
 }
 
 impl FromStr for PaperGrid {
-    type Err = Infallible;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut rolls = HashSet::default();
-        for (y, line) in s.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                if c == '@' {
-                    rolls.insert(Vec2D::new(x as i64, y as i64));
-                }
-            }
-        }
-        Ok(Self(rolls))
+        let grid = BitGrid::parse(s, |c| match c {
+            '@' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        Ok(Self(grid))
+    }
+}
+
+/// Generates a `size` x `size` grid where roughly 60% of the cells hold a roll.
+impl Generator for Day04 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.chance(0.6) { '@' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::{read_input, sample_path};
 
     #[test]
     fn test_part1() {
@@ -102,4 +185,31 @@ mod tests {
         let answer = Day04.run_test2();
         assert_eq!(answer, 43);
     }
+
+    #[test]
+    fn test_visualize() {
+        let input = read_input(&sample_path(4, 1)).unwrap();
+        let frames = Day04::visualize(&input).unwrap();
+        assert_eq!(frames.len(), 10);
+        assert!(frames[0].to_text().starts_with("..xx.xx@x."));
+        assert!(!frames.last().unwrap().to_text().contains('x'));
+    }
+
+    #[test]
+    fn test_accessible_rolls_match_scalar_count() {
+        let input = Day04.generate(200, &mut Rng::new(4));
+        let grid = PaperGrid::from_str(&input).unwrap();
+        let expected = grid
+            .rolls()
+            .filter(|p| grid.accessible_by_forklift(p))
+            .collect::<Vec<_>>();
+        assert_eq!(grid.accessible_rolls().ones().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_generated_input() {
+        let input = Day04.generate(50, &mut Rng::new(2025));
+        assert!(Day04::part1(&input).is_ok());
+        assert!(Day04::part2(&input).is_ok());
+    }
 }
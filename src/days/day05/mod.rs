@@ -1,6 +1,15 @@
 use std::{ops::RangeInclusive, str::FromStr};
 
-use crate::solution::Solution;
+use crate::{
+    generator::Generator,
+    repl::Inspect,
+    solution::Solution,
+    utils::{
+        interval_set::IntervalSet,
+        parse::{ParseError, parse_at, parse_lines, parse_range, section_pair},
+        rng::Rng,
+    },
+};
 use anyhow::Result;
 
 pub struct Day05;
@@ -12,44 +21,84 @@ impl Solution for Day05 {
         5
     }
 
-    fn part1(_input: &str) -> Result<Self::Answer> {
-        let inventory = Inventory::from_str(_input)?;
-        Ok(inventory.count_fresh_ingredients())
+    fn part1(input: &str) -> Result<Self::Answer> {
+        Self::part1_parsed(&Self::parse(input)?)
     }
 
-    fn part2(_input: &str) -> Result<Self::Answer> {
-        let inventory = Inventory::from_str(_input)?;
-        Ok(inventory.total_possible_fresh_ingredients())
+    fn part2(input: &str) -> Result<Self::Answer> {
+        Self::part2_parsed(&Self::parse(input)?)
     }
 }
 
-struct Inventory {
+impl Inspect for Day05 {
+    type Parsed = Inventory;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
+    }
+
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        Ok(parsed.count_fresh_ingredients())
+    }
+
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        u64::try_from(parsed.total_possible_fresh_ingredients())
+            .map_err(|_| anyhow::anyhow!("Every ID is fresh, which is more than a u64 can count"))
+    }
+
+    fn structures() -> &'static [&'static str] {
+        &["ranges", "merged", "ingredients", "fresh"]
+    }
+
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String> {
+        let format_ranges = |ranges: &mut dyn Iterator<Item = RangeInclusive<u64>>| {
+            ranges
+                .map(|range| format!("{}-{}", range.start(), range.end()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let dump = match structure {
+            "ranges" => format_ranges(&mut parsed.fresh_ranges.iter().cloned()),
+            "merged" => format_ranges(&mut parsed.fresh.iter()),
+            "ingredients" => parsed
+                .ingredient_list
+                .iter()
+                .map(|ingredient| ingredient.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "fresh" => parsed
+                .ingredient_list
+                .iter()
+                .filter(|&&ingredient| parsed.is_fresh(ingredient))
+                .map(|ingredient| ingredient.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => return None,
+        };
+        Some(dump)
+    }
+}
+
+pub struct Inventory {
+    /// The fresh ranges as listed in the input
     fresh_ranges: Vec<RangeInclusive<u64>>,
+    /// The same ranges merged, for lookups
+    fresh: IntervalSet,
     ingredient_list: Vec<u64>,
 }
 
 impl FromStr for Inventory {
-    type Err = anyhow::Error;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut fresh_ranges = Vec::new();
-        let mut ingredient_list = Vec::new();
-
-        let (fresh_ranges_str, ingredient_list_str) = s
-            .split_once("\n\n")
-            .ok_or(anyhow::anyhow!("Invalid input"))?;
-        for range_str in fresh_ranges_str.lines() {
-            let (start, end) = range_str
-                .split_once('-')
-                .ok_or(anyhow::anyhow!("Invalid range"))?;
-            let start = start.parse::<u64>()?;
-            let end = end.parse::<u64>()?;
-            fresh_ranges.push(start..=end);
-        }
-        for ingredient_str in ingredient_list_str.lines() {
-            let ingredient = ingredient_str.parse::<u64>()?;
-            ingredient_list.push(ingredient);
-        }
+        let (fresh_ranges_str, ingredient_list_str) = section_pair(s)?;
+        let fresh_ranges = parse_lines(fresh_ranges_str, parse_range)
+            .map_err(|err| err.within(s, fresh_ranges_str))?;
+        let ingredient_list = parse_lines(ingredient_list_str, |ingredient_str| {
+            parse_at::<u64>(ingredient_str, ingredient_str)
+        })
+        .map_err(|err| err.within(s, ingredient_list_str))?;
         Ok(Self {
+            fresh: fresh_ranges.iter().cloned().collect(),
             fresh_ranges,
             ingredient_list,
         })
@@ -58,9 +107,7 @@ impl FromStr for Inventory {
 
 impl Inventory {
     fn is_fresh(&self, ingredient: u64) -> bool {
-        self.fresh_ranges
-            .iter()
-            .any(|range| range.contains(&ingredient))
+        self.fresh.contains(ingredient)
     }
 
     fn count_fresh_ingredients(&self) -> u64 {
@@ -70,23 +117,28 @@ impl Inventory {
             .count() as u64
     }
 
-    fn total_possible_fresh_ingredients(mut self) -> u64 {
-        self.fresh_ranges.sort_by_key(|range| *range.start());
-        let mut ranges_merged: Vec<RangeInclusive<u64>> = Vec::new();
-
-        for range in self.fresh_ranges {
-            if let Some(last_range) = ranges_merged.last_mut() {
-                if range.start() <= last_range.end() {
-                    *last_range = *last_range.start()..=*range.end().max(last_range.end());
-                    continue;
-                }
-            }
-            ranges_merged.push(range);
-        }
-        ranges_merged
-            .iter()
-            .map(|range| range.end() - range.start() + 1)
-            .sum::<u64>()
+    fn total_possible_fresh_ingredients(&self) -> u128 {
+        self.fresh.total_length()
+    }
+}
+
+/// Generates `size` (possibly overlapping) fresh ranges followed by `size` ingredient IDs.
+impl Generator for Day05 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        const MAX_ID: i64 = 1_000_000_000_000_000;
+        let ranges = (0..size)
+            .map(|_| {
+                let start = rng.range(1..MAX_ID);
+                let end = (start + rng.range(0..MAX_ID / 1000)).min(MAX_ID);
+                format!("{}-{}", start, end)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let ingredients = (0..size)
+            .map(|_| rng.range(1..=MAX_ID).to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("{}\n\n{}", ranges, ingredients)
     }
 }
 
@@ -105,4 +157,27 @@ mod tests {
         let answer = Day05.run_test2();
         assert_eq!(answer, 14); // TODO: Update with expected answer
     }
+
+    #[test]
+    fn test_generated_input() {
+        let input = Day05.generate(200, &mut Rng::new(2025));
+        assert!(Day05::part1(&input).is_ok());
+        assert!(Day05::part2(&input).is_ok());
+    }
+
+    #[test]
+    fn test_fresh_ranges_up_to_u64_max() {
+        assert_eq!(
+            Day05::part2("1-18446744073709551615\n\n1").unwrap(),
+            u64::MAX
+        );
+        assert!(Day05::part2("0-18446744073709551615\n\n1").is_err());
+    }
+
+    #[test]
+    fn test_parse_error_points_at_ingredient() {
+        let err = Inventory::from_str("3-5\n10-14\n\n1\n5x\n8").err().unwrap();
+        assert_eq!((err.line, err.column), (5, 1));
+        assert_eq!(err.excerpt, "5x");
+    }
 }
@@ -1,6 +1,14 @@
 use std::str::FromStr;
 
-use crate::solution::Solution;
+use crate::{
+    generator::Generator,
+    repl::Inspect,
+    solution::Solution,
+    utils::{
+        parse::{ParseError, chars, parse_lines, words},
+        rng::Rng,
+    },
+};
 use anyhow::Result;
 
 pub struct Day06;
@@ -13,15 +21,71 @@ impl Solution for Day06 {
     }
 
     fn part1(input: &str) -> Result<Self::Answer> {
-        let worksheet = Worksheet::from_str(input)?;
+        Ok(Worksheet::from_str(input)?.grand_total())
+    }
+
+    fn part2(input: &str) -> Result<Self::Answer> {
+        Ok(WorksheetV2::from_str(input)?.grand_total())
+    }
+}
+
+impl Inspect for Day06 {
+    type Parsed = (Worksheet, WorksheetV2);
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok((input.parse()?, input.parse()?))
+    }
+
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        Ok(parsed.0.grand_total())
+    }
+
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        Ok(parsed.1.grand_total())
+    }
+
+    fn structures() -> &'static [&'static str] {
+        &["operations", "rows", "columns"]
+    }
+
+    fn dump((worksheet, worksheet_v2): &Self::Parsed, structure: &str) -> Option<String> {
+        let dump = match structure {
+            "operations" => format!("{:?}", worksheet.operations),
+            "rows" => worksheet
+                .numbers
+                .iter()
+                .map(|row| format!("{:?}", row))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "columns" => worksheet_v2
+                .read_columns()
+                .iter()
+                .zip(&worksheet_v2.operations)
+                .map(|(column, operation)| format!("{:?} {:?}", operation, column))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => return None,
+        };
+        Some(dump)
+    }
+}
+
+pub struct Worksheet {
+    operations: Vec<Operation>,
+    numbers: Vec<Vec<u64>>,
+}
+
+impl Worksheet {
+    /// Sum of the results of all problems, reading numbers along the rows
+    fn grand_total(&self) -> u64 {
         let mut results = Vec::new();
-        for (i, operation) in worksheet.operations.iter().enumerate() {
+        for (i, operation) in self.operations.iter().enumerate() {
             // println!("Operation: {:?}, Index: {}", operation, i);
             let mut result = match operation {
                 Operation::Add => 0,
                 Operation::Multiply => 1,
             };
-            for value in worksheet.numbers.iter().map(|row| row[i]) {
+            for value in self.numbers.iter().map(|row| row[i]) {
                 // println!("Value: {}", value);
                 match operation {
                     Operation::Add => result += value,
@@ -31,63 +95,28 @@ impl Solution for Day06 {
             // println!("Result: {}", result);
             results.push(result);
         }
-        Ok(results.iter().sum())
-    }
-
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let worksheet = WorksheetV2::from_str(input)?;
-        let parsed = worksheet.read_columns();
-
-        let results: Vec<u64> = parsed
-            .iter()
-            .enumerate()
-            .map(|(i, list)| {
-                list.iter().fold(
-                    match worksheet.operations[i] {
-                        Operation::Add => 0,
-                        Operation::Multiply => 1,
-                    },
-                    |acc, &num| match worksheet.operations[i] {
-                        Operation::Add => acc + num,
-                        Operation::Multiply => acc * num,
-                    },
-                )
-            })
-            .collect();
-
-        Ok(results.iter().sum())
+        results.iter().sum()
     }
 }
 
-struct Worksheet {
-    operations: Vec<Operation>,
-    numbers: Vec<Vec<u64>>,
-}
-
 impl FromStr for Worksheet {
-    type Err = anyhow::Error;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines = s.lines().collect::<Vec<&str>>();
-        let operations = lines
-            .iter()
-            .last()
-            .ok_or(anyhow::anyhow!("No operations"))?
-            .split_whitespace()
-            .map(|op| match op {
-                "+" => Ok(Operation::Add),
-                "*" => Ok(Operation::Multiply),
-                _ => return Err(anyhow::anyhow!("Invalid operation: {}", op)),
-            })
-            .collect::<Result<Vec<Operation>>>()?;
-        let numbers = lines
-            .iter()
-            .take(lines.len() - 1)
-            .map(|line| {
-                line.split_whitespace()
-                    .map(|num| num.parse::<u64>().unwrap())
-                    .collect::<Vec<u64>>()
-            })
-            .collect::<Vec<Vec<u64>>>();
+        let (data, operations_line) = split_operations(s);
+        let operations =
+            parse_operations(operations_line).map_err(|err| err.within(s, operations_line))?;
+        let numbers = parse_lines(data, |line| {
+            let row = words::<u64>(line)?;
+            if row.len() != operations.len() {
+                return Err(ParseError::at(
+                    line,
+                    line,
+                    format!("Expected {} numbers, found {}", operations.len(), row.len()),
+                ));
+            }
+            Ok(row)
+        })
+        .map_err(|err| err.within(s, data))?;
         Ok(Self {
             operations,
             numbers,
@@ -95,38 +124,59 @@ impl FromStr for Worksheet {
     }
 }
 
+/// Splits the worksheet into its number rows and the trailing operations line
+fn split_operations(s: &str) -> (&str, &str) {
+    s.trim_end_matches('\n')
+        .rsplit_once('\n')
+        .unwrap_or(("", s))
+}
+
+fn parse_operations(line: &str) -> Result<Vec<Operation>, ParseError> {
+    let operations = words::<Operation>(line)?;
+    if operations.is_empty() {
+        return Err(ParseError::at(line, line, "No operations"));
+    }
+    Ok(operations)
+}
+
 #[derive(Debug)]
 enum Operation {
     Add,
     Multiply,
 }
 
-struct WorksheetV2 {
+impl FromStr for Operation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" => Ok(Operation::Add),
+            "*" => Ok(Operation::Multiply),
+            _ => Err("Invalid operation".to_string()),
+        }
+    }
+}
+
+pub struct WorksheetV2 {
     operations: Vec<Operation>,
     rows: Vec<String>,
     max_width: usize,
 }
 
 impl FromStr for WorksheetV2 {
-    type Err = anyhow::Error;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines = s.lines().collect::<Vec<&str>>();
-
         // Parse operations from the last line
-        let operations = lines
-            .iter()
-            .last()
-            .ok_or(anyhow::anyhow!("No operations"))?
-            .split_whitespace()
-            .map(|op| match op {
-                "+" => Ok(Operation::Add),
-                "*" => Ok(Operation::Multiply),
-                _ => Err(anyhow::anyhow!("Invalid operation: {}", op)),
-            })
-            .collect::<Result<Vec<Operation>>>()?;
+        let (data, operations_line) = split_operations(s);
+        let operations =
+            parse_operations(operations_line).map_err(|err| err.within(s, operations_line))?;
 
         // Get all data rows (all lines except the last one)
-        let data_lines: Vec<&str> = lines.iter().take(lines.len() - 1).copied().collect();
+        let data_lines: Vec<&str> = data.lines().collect();
+        for line in &data_lines {
+            chars(line, |c| (c.is_ascii_digit() || c == ' ').then_some(()))
+                .map_err(|err| err.within(s, line))?;
+        }
 
         // Find the maximum width
         let max_width = data_lines.iter().map(|line| line.len()).max().unwrap_or(0);
@@ -146,6 +196,30 @@ impl FromStr for WorksheetV2 {
 }
 
 impl WorksheetV2 {
+    /// Sum of the results of all problems, reading numbers down the columns
+    fn grand_total(&self) -> u64 {
+        let parsed = self.read_columns();
+
+        let results: Vec<u64> = parsed
+            .iter()
+            .enumerate()
+            .map(|(i, list)| {
+                list.iter().fold(
+                    match self.operations[i] {
+                        Operation::Add => 0,
+                        Operation::Multiply => 1,
+                    },
+                    |acc, &num| match self.operations[i] {
+                        Operation::Add => acc + num,
+                        Operation::Multiply => acc * num,
+                    },
+                )
+            })
+            .collect();
+
+        results.iter().sum()
+    }
+
     /// Read numbers column by column and group them by empty columns
     fn read_columns(&self) -> Vec<Vec<u64>> {
         let mut parsed: Vec<Vec<u64>> = vec![Vec::new(); self.operations.len()];
@@ -181,6 +255,45 @@ impl WorksheetV2 {
     }
 }
 
+/// Generates a worksheet of `size` problems over 4 rows of numbers.
+/// Numbers never contain a 0 digit, as part 2 treats those columns as separators.
+impl Generator for Day06 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        const ROWS: usize = 4;
+        let mut rows = vec![String::new(); ROWS];
+        let mut operations = String::new();
+
+        for problem in 0..size {
+            if problem > 0 {
+                rows.iter_mut().for_each(|row| row.push(' '));
+                operations.push(' ');
+            }
+            let width = rng.range_usize(1..=4);
+            let widest_row = rng.range_usize(..ROWS);
+            let right_aligned = rng.chance(0.5);
+            for (i, row) in rows.iter_mut().enumerate() {
+                let length = if i == widest_row {
+                    width
+                } else {
+                    rng.range_usize(1..=width)
+                };
+                let number = (0..length)
+                    .map(|_| char::from(b'0' + rng.range(1..=9) as u8))
+                    .collect::<String>();
+                if right_aligned {
+                    row.push_str(&format!("{:>width$}", number));
+                } else {
+                    row.push_str(&format!("{:<width$}", number));
+                }
+            }
+            operations.push_str(&format!("{:<width$}", rng.choose(&['+', '*'])));
+        }
+
+        rows.push(operations);
+        rows.join("\n")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,4 +309,17 @@ mod tests {
         let answer = Day06.run_test2();
         assert_eq!(answer, 3263827); // TODO: Update with expected answer
     }
+
+    #[test]
+    fn test_generated_input() {
+        let input = Day06.generate(100, &mut Rng::new(2025));
+        assert!(Day06::part1(&input).is_ok());
+        assert!(Day06::part2(&input).is_ok());
+    }
+
+    #[test]
+    fn test_parse_error_on_invalid_number() {
+        let err = Worksheet::from_str("1 2\n3 x\n+ *").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
 }
@@ -1,11 +1,20 @@
-use std::{
-    collections::{HashMap, HashSet},
-    str::FromStr,
-};
+use std::{collections::HashSet, str::FromStr};
 
 use crate::{
+    generator::Generator,
+    repl::Inspect,
     solution::Solution,
-    utils::vec2d::{Vec2D, ZERO},
+    utils::{
+        big_uint::{Count, Overflow},
+        direction::Direction,
+        grid::Grid,
+        memo::Memo,
+        parse::ParseError,
+        rng::Rng,
+        simulation::Simulation,
+        vec2d::{self, Vec2D},
+        viz::{Color, Scene, Style},
+    },
 };
 use anyhow::Result;
 
@@ -21,15 +30,76 @@ impl Solution for Day07 {
     /// Part 1: Count the number of times a beam splits as it travels down
     /// the manifold, encountering splitters that cause it to branch left and right.
     fn part1(input: &str) -> Result<Self::Answer> {
-        let manifold = TachyonManifold::from_str(input)?;
-        Ok(manifold.simulate_beam() as u64)
+        Self::part1_parsed(&Self::parse(input)?)
     }
 
     /// Part 2: Count the total number of quantum particles at the end,
     /// where each particle can be in a superposition of multiple beams.
+    /// Fails rather than wrapping if the count doesn't fit in a u64.
     fn part2(input: &str) -> Result<Self::Answer> {
+        Self::part2_parsed(&Self::parse(input)?)
+    }
+
+    /// One frame per row, showing the beams as they spread through the splitters.
+    fn visualize(input: &str) -> Result<Vec<Scene>> {
         let manifold = TachyonManifold::from_str(input)?;
-        Ok(manifold.simulate_quantum_particle() as u64)
+        let bounds = (
+            vec2d::ZERO,
+            Vec2D::new(
+                manifold.grid.width() as i64 - 1,
+                manifold.grid.height() as i64 - 1,
+            ),
+        );
+        let splitters = manifold.splitters().collect::<Vec<_>>();
+
+        let mut lit = HashSet::new();
+        let mut frames = Vec::new();
+        Simulation::new(manifold.start_front(), |front| {
+            manifold.advance_beams(front)
+        })
+        .with_hook(|_, front| {
+            lit.extend(front.beams.iter().map(|&beam| Vec2D::new(beam, front.row)));
+            let mut frame = Scene::new().with_bounds(bounds.0, bounds.1);
+            frame.draw(&lit, Style::new('|', Color::BLUE));
+            frame.draw(&splitters, Style::new('^', Color::YELLOW));
+            frame.set(manifold.start, Style::new('S', Color::GREEN));
+            frames.push(frame);
+        })
+        .run_steps(manifold.rows() - 1);
+        Ok(frames)
+    }
+}
+
+impl Inspect for Day07 {
+    type Parsed = TachyonManifold;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
+    }
+
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        Ok(parsed.simulate_beam())
+    }
+
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        Ok(parsed.simulate_quantum_particle::<u64>()?)
+    }
+
+    fn structures() -> &'static [&'static str] {
+        &["start", "splitters"]
+    }
+
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String> {
+        let dump = match structure {
+            "start" => parsed.start.to_string(),
+            "splitters" => parsed
+                .splitters()
+                .map(|splitter| splitter.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => return None,
+        };
+        Some(dump)
     }
 }
 
@@ -38,22 +108,49 @@ impl Solution for Day07 {
 /// The manifold is traversed from top to bottom, starting at `start` and moving downward.
 /// When a beam encounters a splitter (marked with '^'), it splits into two beams
 /// going left (x-1) and right (x+1) on the next row.
-struct TachyonManifold {
+pub struct TachyonManifold {
     /// The starting position of the beam/particle
     start: Vec2D,
-    /// Set of positions containing splitters (marked with '^' in the input)
-    splitters: HashSet<Vec2D>,
-    /// The total height (number of rows) of the manifold
-    height: usize,
+    /// The cells of the manifold, its height is the number of rows to simulate
+    grid: Grid<Cell>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    /// Marked with '^' in the input
+    Splitter,
+    /// Marked with 'S' in the input
+    Start,
 }
 
 impl TachyonManifold {
-    /// Creates a new TachyonManifold with the given starting position, splitters, and height.
-    fn new(start: Vec2D, splitters: HashSet<Vec2D>, height: usize) -> Self {
-        Self {
-            start,
-            splitters,
-            height,
+    /// Creates a new TachyonManifold from its grid, which must contain a start.
+    fn new(grid: Grid<Cell>) -> Option<Self> {
+        let start = grid.find(&Cell::Start)?;
+        Some(Self { start, grid })
+    }
+
+    fn is_splitter(&self, position: Vec2D) -> bool {
+        self.grid.get(&position) == Some(&Cell::Splitter)
+    }
+
+    /// Positions of all splitters, row by row
+    fn splitters(&self) -> impl Iterator<Item = Vec2D> + '_ {
+        self.grid.find_all(&Cell::Splitter)
+    }
+
+    /// Number of rows from the start to the bottom, the start's own row included
+    fn rows(&self) -> usize {
+        (self.grid.height() as i64 - self.start.y()) as usize
+    }
+
+    /// The single beam leaving the start, before any splits
+    fn start_front(&self) -> BeamFront {
+        BeamFront {
+            row: self.start.y(),
+            beams: HashSet::from([self.start.x()]),
+            splits: 0,
         }
     }
 
@@ -65,80 +162,103 @@ impl TachyonManifold {
     /// and each can split independently.
     ///
     /// Returns the total number of splits that occurred.
-    fn simulate_beam(self) -> u64 {
-        // Track the x-coordinates of all active beams at the current row
-        let mut beams = HashSet::from([self.start.x]);
-        let mut splits = 0;
-        let start_row = self.start.y;
-
-        // Process each row from start to bottom
-        for row in start_row..self.height as i64 {
-            let mut next_beams = HashSet::new();
-
-            // For each active beam at this row
-            for &beam in beams.iter() {
-                if self.splitters.contains(&Vec2D::new(beam, row)) {
-                    // Beam hits a splitter: count the split and create two new beams
-                    splits += 1;
-                    next_beams.insert(beam + 1); // Right beam
-                    next_beams.insert(beam - 1); // Left beam
-                } else {
-                    // No splitter: beam continues straight down
-                    next_beams.insert(beam);
-                }
+    fn simulate_beam(&self) -> u64 {
+        let mut simulation = Simulation::new(self.start_front(), |front| self.advance_beams(front));
+        simulation.run_steps(self.rows());
+        simulation.into_state().splits
+    }
+
+    /// Moves the beams down one row, counting the splits along the way.
+    fn advance_beams(&self, front: &BeamFront) -> BeamFront {
+        let mut beams = HashSet::new();
+        let mut splits = front.splits;
+
+        // For each active beam at this row
+        for &beam in front.beams.iter() {
+            if self.is_splitter(Vec2D::new(beam, front.row)) {
+                // Beam hits a splitter: count the split and create two new beams
+                splits += 1;
+                beams.extend(split(beam));
+            } else {
+                // No splitter: beam continues straight down
+                beams.insert(beam);
             }
-            beams = next_beams;
         }
-        splits
+        BeamFront {
+            row: front.row + 1,
+            beams,
+            splits,
+        }
     }
 
     /// Simulates a quantum particle traveling down the manifold in superposition.
     ///
-    /// Unlike Part 1 where we only count splits, here we track the actual number
-    /// of particles in each beam position. When a particle encounters a splitter,
-    /// it creates a superposition where the particle exists in both the left and
-    /// right beams simultaneously.
+    /// Unlike Part 1 where we only count splits, here we count the timelines a
+    /// particle ends up in. A particle falls straight down to the next splitter
+    /// in its column, and is in one timeline if there is none; on a splitter it
+    /// is in every timeline of the left and the right beam. The recursion is
+    /// memoized per splitter, since beams merge again below.
+    ///
+    /// Counts can double at every splitter, so they are kept in `C` with overflow
+    /// checks; use `BigUint` for manifolds where a `u64` isn't enough.
     ///
     /// Returns the total count of particles across all beams at the bottom of the manifold.
-    fn simulate_quantum_particle(self) -> u64 {
-        // Map from beam x-coordinate to the count of particles in that beam
-        let mut particles = HashMap::from([(self.start.x, 1)]);
-
-        // Process each row from start to bottom
-        for row in self.start.y..self.height as i64 {
-            let mut next_particles = HashMap::new();
-
-            // For each beam and its particle count
-            for (beam, count) in particles {
-                if self.splitters.contains(&Vec2D::new(beam, row)) {
-                    // Particle hits a splitter: split into left and right beams
-                    // Each beam receives the same count of particles
-                    next_particles
-                        .entry(beam + 1) // Right beam
-                        .and_modify(|c| *c += count)
-                        .or_insert(count);
-                    next_particles
-                        .entry(beam - 1) // Left beam
-                        .and_modify(|c| *c += count)
-                        .or_insert(count);
-                } else {
-                    // No splitter: particles continue straight down
-                    next_particles
-                        .entry(beam)
-                        .and_modify(|c| *c += count)
-                        .or_insert(count);
-                }
-            }
-            particles = next_particles;
+    fn simulate_quantum_particle<C: Count>(&self) -> Result<C, Overflow> {
+        let splitters = self.splitters().collect::<Vec<_>>();
+        // Rows of the splitters in each column, top to bottom
+        let mut columns = vec![Vec::new(); self.grid.width()];
+        for splitter in &splitters {
+            columns[splitter.x() as usize].push(splitter.y());
         }
+        let landing = |x: i64, y: i64| {
+            let rows = columns.get(usize::try_from(x).ok()?)?;
+            let below = rows.partition_point(|&row| row < y);
+            rows.get(below).map(|&row| Vec2D::new(x, row))
+        };
 
-        // Return the total count of all particles across all final beams
-        particles.values().sum()
+        let mut timelines = Memo::new(
+            |timelines: &mut dyn FnMut(Vec2D) -> Result<C, Overflow>, splitter: Vec2D| {
+                // The particle is in the left and right beams at once
+                split(splitter.x())
+                    .iter()
+                    .try_fold(C::zero(), |total, &beam| {
+                        let particles = match landing(beam, splitter.y() + 1) {
+                            Some(next) => timelines(next)?,
+                            None => C::one(),
+                        };
+                        total.try_add(&particles)
+                    })
+            },
+        );
+        // Asking for the bottom splitters first keeps the recursion shallow
+        // however tall the manifold is
+        for &splitter in splitters.iter().rev() {
+            timelines.get(splitter)?;
+        }
+        let particles = match landing(self.start.x(), self.start.y()) {
+            Some(splitter) => timelines.get(splitter),
+            None => Ok(C::one()),
+        };
+        timelines.log_stats("Quantum particle timelines");
+        particles
     }
 }
 
+/// The beams crossing one row of the manifold, and the splits on the way there
+struct BeamFront {
+    row: i64,
+    /// x-coordinates of the active beams
+    beams: HashSet<i64>,
+    splits: u64,
+}
+
+/// The x-coordinates of the two beams a splitter at `beam` sends out
+fn split(beam: i64) -> [i64; 2] {
+    [Direction::Left, Direction::Right].map(|direction| beam + direction.to_vec2d().x())
+}
+
 impl FromStr for TachyonManifold {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     /// Parses the manifold from the input string.
     ///
@@ -147,30 +267,49 @@ impl FromStr for TachyonManifold {
     /// - '^' marks splitter positions
     /// - '.' represents empty space
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut start = ZERO;
-        let mut splitters = HashSet::new();
-        let height = s.lines().count();
-
-        // Parse the grid to find the start position and all splitters
-        for (y, line) in s.lines().enumerate() {
-            for (x, ch) in line.chars().enumerate() {
-                if ch == '^' {
-                    // Found a splitter
-                    splitters.insert(Vec2D::new(x as i64, y as i64));
-                }
-                if ch == 'S' {
-                    // Found the starting position
-                    start = Vec2D::new(x as i64, y as i64);
-                }
-            }
-        }
-        Ok(Self::new(start, splitters, height))
+        let grid = Grid::parse(s, |ch| match ch {
+            '^' => Some(Cell::Splitter),
+            'S' => Some(Cell::Start),
+            '.' => Some(Cell::Empty),
+            _ => None,
+        })?;
+        Self::new(grid).ok_or_else(|| ParseError::at(s, s, "No starting position 'S'"))
+    }
+}
+
+/// Generates a `size` x `size` manifold with the start in the middle of the top row
+/// and splitters scattered on every other row, away from the left and right edges.
+impl Generator for Day07 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let width = size.max(3);
+        (0..size)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        if y == 0 && x == width / 2 {
+                            'S'
+                        } else if y >= 2
+                            && y.is_multiple_of(2)
+                            && x > 0
+                            && x < width - 1
+                            && rng.chance(0.3)
+                        {
+                            '^'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::big_uint::BigUint;
 
     #[test]
     fn test_part1() {
@@ -185,4 +324,57 @@ mod tests {
         let answer = Day07.run_test2();
         assert_eq!(answer, 40);
     }
+
+    /// A manifold where every other row is all splitters, so the particle count
+    /// doubles `levels` times
+    fn doubling_manifold(levels: usize) -> String {
+        let width = 2 * levels + 5;
+        let mut rows = vec![format!(
+            "{}S{}",
+            ".".repeat(width / 2),
+            ".".repeat(width / 2)
+        )];
+        for _ in 0..levels {
+            rows.push(".".repeat(width));
+            rows.push("^".repeat(width));
+        }
+        rows.join("\n")
+    }
+
+    #[test]
+    fn test_part2_overflow() {
+        assert_eq!(Day07::part2(&doubling_manifold(63)).unwrap(), 1 << 63);
+
+        let input = doubling_manifold(70);
+        let err = Day07::part2(&input).unwrap_err();
+        assert_eq!(err.to_string(), "Count overflowed a u64");
+        let particles = TachyonManifold::from_str(&input)
+            .unwrap()
+            .simulate_quantum_particle::<BigUint>()
+            .unwrap();
+        assert_eq!(particles.to_string(), "1180591620717411303424");
+    }
+
+    #[test]
+    fn test_part2_tall_manifold() {
+        // A long empty drop, then levels that each split once in the middle
+        // and once on the left, where one beam rejoins the middle, so every
+        // level adds two timelines
+        let levels = 100_000;
+        let mut rows = vec![".S."];
+        rows.extend(std::iter::repeat_n("...", 100_000));
+        for _ in 0..levels {
+            rows.push(".^.");
+            rows.push("^..");
+        }
+        rows.push("...");
+        assert_eq!(Day07::part2(&rows.join("\n")).unwrap(), 2 * levels + 1);
+    }
+
+    #[test]
+    fn test_generated_input() {
+        let input = Day07.generate(50, &mut Rng::new(2025));
+        assert!(Day07::part1(&input).is_ok());
+        assert!(Day07::part2(&input).is_ok());
+    }
 }
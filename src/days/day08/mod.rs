@@ -1,10 +1,21 @@
 use std::str::FromStr;
 
 use crate::{
+    generator::Generator,
+    params::{self, Parameter},
+    repl::Inspect,
     solution::Solution,
-    utils::vec3d::{Vec3D, ZERO},
+    utils::{
+        kd_tree::KdTree,
+        parse::{ParseError, parse_lines},
+        rng::Rng,
+        union_find::UnionFind,
+        vec3d::{Vec3D, ZERO},
+        vecn::VecN,
+    },
 };
 use anyhow::Result;
+use itertools::Itertools;
 
 pub struct Day08;
 
@@ -16,205 +27,209 @@ impl Solution for Day08 {
     }
 
     fn part1(input: &str) -> Result<Self::Answer> {
-        // Part 1: Find the sizes of the 3 largest junction boxes after 1000 connections.
-        // Strategy: Connect vectors based on their proximity (closest pairs first),
-        // then multiply the sizes of the 3 largest resulting boxes.
-
-        // Parse the input into a junction room where each vector starts in its own box
-        let mut junction_room = input.parse::<JunctionRoom>()?;
-        let all_vectors = junction_room.all_vectors();
+        Self::part1_parsed(&Self::parse(input)?)
+    }
 
-        // Find all possible vector pairs sorted by distance (closest first)
-        let pairs = find_closest_vector_mapping(&all_vectors);
+    fn part2(input: &str) -> Result<Self::Answer> {
+        Self::part2_parsed(&Self::parse(input)?)
+    }
+}
 
-        // Limit connections to 10 for tests, 1000 for the actual puzzle
-        let max_pairs = if cfg!(test) { 10 } else { 1000 };
+impl Inspect for Day08 {
+    type Parsed = JunctionRoom;
 
-        // Process the first max_pairs connections
-        for (i, (v1, v2)) in pairs.iter().enumerate() {
-            log::debug!("Processing pair {}: {} -> {}", i, v1, v2);
-            if i >= max_pairs {
-                break;
-            }
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
+    }
 
-            // Find which boxes contain these vectors
-            let from_index = junction_room.find_box_index(v1);
-            let to_index = junction_room.find_box_index(v2);
-
-            match (from_index, to_index) {
-                // If vectors are in different boxes, merge them
-                (Some(from_index), Some(to_index)) if from_index != to_index => {
-                    log::debug!("Merging box {} into box {}", from_index, to_index);
-                    junction_room.merge_junction_box(from_index, to_index);
-                }
-                // If they're already in the same box, skip
-                (Some(from_index), Some(to_index)) => {
-                    log::debug!("Boxes {} and {} are already merged", from_index, to_index);
-                }
-                _ => {
-                    return Err(anyhow::anyhow!("Failed to find box index"));
-                }
-            }
-        }
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        // Part 1: Find the sizes of the 3 largest junction boxes after 1000 connections.
+        // Strategy: Connect vectors based on their proximity (closest pairs first),
+        // then multiply the sizes of the 3 largest resulting boxes.
 
-        // Sort boxes by size (largest first) and calculate score
-        junction_room.sort_by_box_size();
+        // Start from the parsed junction room, where each vector is in its own box
+        let mut junction_room = parsed.clone();
+        junction_room.connect_closest(connections());
 
         // Score is the product of the 3 largest box sizes
         Ok(junction_room.score())
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
         // Part 2: Find the last junction box connection needed to connect all vectors
         // into one large box. The answer is the product of the x-coordinates of the
         // two vectors involved in the final connection.
 
-        // Parse input and find all pairs sorted by distance
-        let mut junction_room = input.parse::<JunctionRoom>()?;
-        let pairs = find_closest_vector_mapping(&junction_room.all_vectors());
+        // Start from the parsed junction room and walk the pairs in order of distance
+        let mut junction_room = parsed.clone();
+        let pairs = find_closest_pairs(&junction_room.vectors);
 
         // Track the last successful merge
         let mut last_from = ZERO;
         let mut last_to = ZERO;
 
         // Keep connecting boxes until only one remains
-        for (from_vector, to_vector) in pairs {
+        for (from, to) in pairs {
             // Stop when all vectors are in a single box
-            if junction_room.0.len() == 1 {
+            if junction_room.boxes.component_count() == 1 {
                 break;
             }
 
-            // Find which boxes contain these vectors
-            let from_index = junction_room.find_box_index(&from_vector);
-            let to_index = junction_room.find_box_index(&to_vector);
-
-            match (from_index, to_index) {
-                // If vectors are in different boxes, merge them
-                (Some(from_index), Some(to_index)) if from_index != to_index => {
-                    log::debug!(
-                        "Merging box {} ({}) into box {} ({})",
-                        from_index,
-                        from_vector,
-                        to_index,
-                        to_vector
-                    );
-                    junction_room.merge_junction_box(from_index, to_index);
-
-                    // Remember this connection as it might be the last one
-                    last_to = to_vector;
-                    last_from = from_vector;
-                }
-                // If they're already in the same box, skip
-                (Some(from_index), Some(to_index)) => {
-                    log::debug!("Boxes {} and {} are already merged", from_index, to_index);
-                }
-                _ => {
-                    return Err(anyhow::anyhow!("Failed to find box index"));
-                }
+            let (from_vector, to_vector) = (junction_room.vectors[from], junction_room.vectors[to]);
+            if junction_room.boxes.union(from, to) {
+                log::debug!("Merging boxes of {} and {}", from_vector, to_vector);
+
+                // Remember this connection as it might be the last one
+                last_to = to_vector;
+                last_from = from_vector;
+            } else {
+                log::debug!(
+                    "{} and {} are already in the same box",
+                    from_vector,
+                    to_vector
+                );
             }
         }
 
+        if junction_room.boxes.component_count() > 1 {
+            return Err(anyhow::anyhow!("Could not connect all junction boxes"));
+        }
+
         // Answer is the product of the x-coordinates of the last connection
         log::debug!(
             "{} * {} = {}",
-            last_from.x,
-            last_to.x,
-            last_from.x * last_to.x
+            last_from.x(),
+            last_to.x(),
+            last_from.x() * last_to.x()
         );
-        Ok((last_from.x * last_to.x) as u64)
+        Ok((last_from.x() * last_to.x()) as u64)
+    }
+
+    fn structures() -> &'static [&'static str] {
+        &["vectors", "pairs", "boxes"]
+    }
+
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String> {
+        let dump = match structure {
+            "vectors" => parsed.vectors.iter().join("\n"),
+            "pairs" => find_closest_vector_mapping(&parsed.vectors)
+                .take(connections())
+                .map(|(v1, v2)| format!("{} <-> {} ({})", v1, v2, v1.square_distance_to(&v2)))
+                .join("\n"),
+            "boxes" => {
+                let mut room = parsed.clone();
+                room.connect_closest(connections());
+                room.boxes.component_sizes().iter().join("\n")
+            }
+            _ => return None,
+        };
+        Some(dump)
+    }
+
+    fn parameters() -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[Parameter::new::<usize>(
+            "connections",
+            "closest pairs to connect in part 1",
+        )];
+        PARAMETERS
     }
 }
 
 /// Represents a junction room containing multiple junction boxes.
-/// Each junction box is a collection of 3D vectors that are connected together.
+/// Each junction box is a set of 3D vectors that are connected together,
+/// tracked as components of a disjoint-set over the vectors' indices.
 /// Initially, each vector starts in its own separate box.
 #[derive(Clone, Debug)]
-struct JunctionRoom(Vec<Vec<Vec3D>>);
+pub struct JunctionRoom {
+    vectors: Vec<Vec3D>,
+    boxes: UnionFind,
+}
 
 impl JunctionRoom {
     /// Creates a new junction room where each vector starts in its own box
     fn new(vectors: Vec<Vec3D>) -> Self {
-        Self(vectors.into_iter().map(|v| vec![v]).collect())
+        let boxes = UnionFind::new(vectors.len());
+        Self { vectors, boxes }
     }
 
-    /// Merge two junction boxes into one, consuming the first box.
-    /// Combines all vectors from the 'from' box into the 'to' box,
-    /// then removes the 'from' box from the room.
-    fn merge_junction_box(&mut self, from: usize, to: usize) {
-        let from_box = &self.0[from];
-        let to_box = &self.0[to];
-        let merged_box = from_box.iter().chain(to_box.iter()).collect::<Vec<_>>();
-        self.0[to] = merged_box.into_iter().cloned().collect();
-        self.0.remove(from);
-    }
-
-    /// Sort junction boxes by size in descending order (largest first)
-    fn sort_by_box_size(&mut self) {
-        self.0.sort_by_key(|junction_box| junction_box.len());
-        self.0.reverse();
-    }
-
-    /// Find which box contains a given vector
-    fn find_box_index(&self, vector: &Vec3D) -> Option<usize> {
-        self.0
-            .iter()
-            .position(|junction_box| junction_box.contains(vector))
+    /// Connects the `max_pairs` closest pairs of vectors, merging their boxes
+    fn connect_closest(&mut self, max_pairs: usize) {
+        // Only the first max_pairs of the pairs (closest first) are ever computed
+        let pairs = find_closest_pairs(&self.vectors);
+        for (i, (from, to)) in pairs.take(max_pairs).enumerate() {
+            log::debug!(
+                "Processing pair {}: {} -> {}",
+                i,
+                self.vectors[from],
+                self.vectors[to]
+            );
+            if !self.boxes.union(from, to) {
+                log::debug!("Vectors {} and {} are already in the same box", from, to);
+            }
+        }
     }
 
     /// Calculate the score as the product of the sizes of the 3 largest boxes
     fn score(&self) -> u64 {
-        self.0
+        self.boxes
+            .component_sizes()
             .iter()
             .take(3)
-            .map(|junction_box| junction_box.len() as u64)
+            .map(|&size| size as u64)
             .product()
     }
-
-    /// Get a flat list of all vectors in all boxes
-    fn all_vectors(&self) -> Vec<Vec3D> {
-        self.0.iter().flatten().cloned().collect()
-    }
 }
 
 impl FromStr for JunctionRoom {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let vectors = s
-            .lines()
-            .map(|line| line.parse::<Vec3D>())
-            .collect::<Result<Vec<_>>>()?;
+        let vectors = parse_lines(s, Vec3D::from_str)?;
         Ok(Self::new(vectors))
     }
 }
 
-/// Given a list of vectors, return all possible pairs sorted by their distance.
-/// This creates a "connection plan" where closest vectors are connected first.
-/// Uses squared distance for efficiency (avoids square root calculations).
-fn find_closest_vector_mapping(vectors: &[Vec3D]) -> Vec<(Vec3D, Vec3D)> {
-    log::debug!("Finding all vector pairs for: {:?}", vectors);
-    let mut pairs = Vec::new();
-
-    // Generate all unique pairs (combinations, not permutations)
-    // For n vectors, this produces n*(n-1)/2 pairs
-    for i in 0..vectors.len() {
-        for j in i + 1..vectors.len() {
-            let v1 = vectors[i];
-            let v2 = vectors[j];
-            pairs.push((v1, v2));
-        }
-    }
+/// Number of connections to make in part 1: 10 for the sample, 1000 for the actual puzzle.
+/// Can be overridden with the `connections` parameter.
+fn connections() -> usize {
+    params::get_or("connections", if cfg!(test) { 10 } else { 1000 })
+}
 
-    // Sort by squared distance (smallest first)
-    // This ensures we connect closest vectors first
-    pairs.sort_by_key(|(v1, v2)| v1.square_distance_to(v2));
+/// Given a list of vectors, return the indices of all possible pairs ordered by
+/// their distance. This creates a "connection plan" where closest vectors are
+/// connected first. Pairs come from a k-d tree as they are needed, so taking
+/// only the first few doesn't build and sort all n*(n-1)/2 of them. Works in
+/// any number of dimensions.
+fn find_closest_pairs<const N: usize>(
+    vectors: &[VecN<N>],
+) -> impl Iterator<Item = (usize, usize)> + use<N> {
+    KdTree::new(vectors).into_closest_pairs()
+}
 
-    pairs
+/// Like `find_closest_pairs`, but yields the vectors themselves
+fn find_closest_vector_mapping<const N: usize>(
+    vectors: &[VecN<N>],
+) -> impl Iterator<Item = (VecN<N>, VecN<N>)> + '_ {
+    find_closest_pairs(vectors).map(|(i, j)| (vectors[i], vectors[j]))
+}
+
+/// Generates `size` junction box positions with coordinates between 0 and 99999.
+impl Generator for Day08 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                let [x, y, z] = [(); 3].map(|_| rng.range(0..100_000));
+                format!("{},{},{}", x, y, z)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::{vec2d::Vec2D, vec3d::Rotation};
 
     fn init_logger() {
         let _ = env_logger::builder()
@@ -240,7 +255,7 @@ mod tests {
     #[test]
     fn test_find_closest_vector_mapping_two_vectors() {
         let vectors = vec![Vec3D::new(0, 0, 0), Vec3D::new(1, 0, 0)];
-        let pairs = find_closest_vector_mapping(&vectors);
+        let pairs = find_closest_vector_mapping(&vectors).collect::<Vec<_>>();
 
         // With 2 vectors, we should have 1 unique pair
         assert_eq!(pairs.len(), 1);
@@ -263,7 +278,7 @@ mod tests {
             Vec3D::new(1, 0, 0),
             Vec3D::new(5, 0, 0),
         ];
-        let pairs = find_closest_vector_mapping(&vectors);
+        let pairs = find_closest_vector_mapping(&vectors).collect::<Vec<_>>();
 
         // With 3 vectors, we should have 3 unique pairs
         assert_eq!(pairs.len(), 3);
@@ -286,7 +301,7 @@ mod tests {
             Vec3D::new(2, 0, 0),    // 4 away from origin (squared distance)
             Vec3D::new(10, 10, 10), // far away
         ];
-        let pairs = find_closest_vector_mapping(&vectors);
+        let pairs = find_closest_vector_mapping(&vectors).collect::<Vec<_>>();
 
         // With 4 vectors, we should have 6 unique pairs (4 choose 2)
         assert_eq!(pairs.len(), 6);
@@ -311,7 +326,7 @@ mod tests {
             Vec3D::new(0, 0, 0),
             Vec3D::new(5, 5, 5),
         ];
-        let pairs = find_closest_vector_mapping(&vectors);
+        let pairs = find_closest_vector_mapping(&vectors).collect::<Vec<_>>();
 
         // With 3 vectors, we should have 3 unique pairs
         assert_eq!(pairs.len(), 3);
@@ -336,148 +351,130 @@ mod tests {
             Vec3D::new(0, 1, 0),
             Vec3D::new(1, 1, 0),
         ];
-        let pairs = find_closest_vector_mapping(&vectors);
+        let pairs = find_closest_vector_mapping(&vectors).collect::<Vec<_>>();
 
         // With 4 vectors, we should have 6 unique pairs (4 choose 2)
         assert_eq!(pairs.len(), 6);
 
         // The first 4 pairs should be the edges (distance² = 1)
         // The last 2 pairs should be the diagonals (distance² = 2)
-        for i in 0..4 {
-            assert_eq!(pairs[i].0.square_distance_to(&pairs[i].1), 1);
+        for (v1, v2) in &pairs[..4] {
+            assert_eq!(v1.square_distance_to(v2), 1);
         }
-        for i in 4..6 {
-            assert_eq!(pairs[i].0.square_distance_to(&pairs[i].1), 2);
+        for (v1, v2) in &pairs[4..6] {
+            assert_eq!(v1.square_distance_to(v2), 2);
         }
     }
 
     #[test]
-    fn test_merge_junction_box_basic() {
-        // Create a JunctionRoom with 3 separate boxes
-        let vectors = vec![
-            Vec3D::new(0, 0, 0),
-            Vec3D::new(1, 1, 1),
-            Vec3D::new(2, 2, 2),
-        ];
-        let mut room = JunctionRoom::new(vectors);
+    fn test_find_closest_vector_mapping_2d_points() {
+        let vectors = vec![Vec2D::new(0, 0), Vec2D::new(3, 4), Vec2D::new(1, 1)];
+        let pairs = find_closest_vector_mapping(&vectors).collect::<Vec<_>>();
 
-        // Initially should have 3 boxes, each with 1 vector
-        assert_eq!(room.0.len(), 3);
-        assert_eq!(room.0[0].len(), 1);
-        assert_eq!(room.0[1].len(), 1);
-        assert_eq!(room.0[2].len(), 1);
-
-        // Merge box 0 into box 1
-        room.merge_junction_box(0, 1);
-
-        // Should now have 2 boxes
-        assert_eq!(room.0.len(), 2);
-        // The box at index 1 (now index 0 after removal) should have 2 vectors
-        assert_eq!(room.0[0].len(), 2);
-        assert!(room.0[0].contains(&Vec3D::new(0, 0, 0)));
-        assert!(room.0[0].contains(&Vec3D::new(1, 1, 1)));
-        // The box at index 2 (now index 1) should still have 1 vector
-        assert_eq!(room.0[1].len(), 1);
-        assert!(room.0[1].contains(&Vec3D::new(2, 2, 2)));
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs[0], (Vec2D::new(0, 0), Vec2D::new(1, 1)));
+        assert_eq!(
+            pairs
+                .iter()
+                .map(|(v1, v2)| v1.square_distance_to(v2))
+                .collect::<Vec<_>>(),
+            vec![2, 13, 25]
+        );
     }
 
     #[test]
-    fn test_merge_junction_box_reverse_order() {
-        // Test merging in reverse order (higher index into lower index)
+    fn test_find_closest_vector_mapping_4d_points() {
         let vectors = vec![
-            Vec3D::new(0, 0, 0),
-            Vec3D::new(1, 1, 1),
-            Vec3D::new(2, 2, 2),
+            VecN::from_array([0, 0, 0, 0]),
+            VecN::from_array([0, 0, 0, 10]),
+            VecN::from_array([1, 1, 1, 1]),
         ];
-        let mut room = JunctionRoom::new(vectors);
+        let pairs = find_closest_vector_mapping(&vectors).collect::<Vec<_>>();
+
+        // (0,0,0,0) <-> (1,1,1,1): distance² = 4
+        // (0,0,0,10) <-> (1,1,1,1): distance² = 84
+        // (0,0,0,0) <-> (0,0,0,10): distance² = 100
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs[0], (vectors[0], vectors[2]));
+        assert_eq!(pairs[1], (vectors[1], vectors[2]));
+        assert_eq!(pairs[2], (vectors[0], vectors[1]));
+    }
 
-        // Merge box 2 into box 0
-        room.merge_junction_box(2, 0);
-
-        // Should now have 2 boxes
-        assert_eq!(room.0.len(), 2);
-        // The box at index 0 should have 2 vectors
-        assert_eq!(room.0[0].len(), 2);
-        assert!(room.0[0].contains(&Vec3D::new(2, 2, 2)));
-        assert!(room.0[0].contains(&Vec3D::new(0, 0, 0)));
-        // The box at index 1 should still be the original box 1
-        assert_eq!(room.0[1].len(), 1);
-        assert!(room.0[1].contains(&Vec3D::new(1, 1, 1)));
+    #[test]
+    fn test_find_closest_vector_mapping_rigid_transform() {
+        // Rotating and shifting the whole cloud keeps every pairwise distance
+        let vectors = Day08::parse(&Day08.generate(20, &mut Rng::new(7)))
+            .unwrap()
+            .vectors;
+        let distances = |vectors: &[Vec3D]| {
+            find_closest_vector_mapping(vectors)
+                .map(|(v1, v2)| v1.square_distance_to(&v2))
+                .collect::<Vec<_>>()
+        };
+        for rotation in Rotation::all() {
+            let moved = vectors
+                .iter()
+                .map(|v| rotation.apply(v) + Vec3D::new(5, -3, 8))
+                .collect::<Vec<_>>();
+            assert_eq!(distances(&moved), distances(&vectors));
+        }
     }
 
     #[test]
-    fn test_merge_junction_box_multiple_vectors() {
-        // Create boxes with multiple vectors each
+    fn test_connect_closest() {
+        // Two clusters far apart, plus a lone vector
         let vectors = vec![
             Vec3D::new(0, 0, 0),
-            Vec3D::new(1, 1, 1),
-            Vec3D::new(2, 2, 2),
+            Vec3D::new(1, 0, 0),
+            Vec3D::new(100, 0, 0),
+            Vec3D::new(2, 0, 0),
+            Vec3D::new(101, 0, 0),
+            Vec3D::new(500, 500, 500),
         ];
         let mut room = JunctionRoom::new(vectors);
-
-        // First, merge box 0 into box 1 to create a box with 2 vectors
-        room.merge_junction_box(0, 1);
-        // Now we have 2 boxes: one with 2 vectors, one with 1 vector
-
-        // Now merge the remaining single-vector box into the multi-vector box
-        room.merge_junction_box(1, 0);
-
-        // Should now have 1 box with all 3 vectors
-        assert_eq!(room.0.len(), 1);
-        assert_eq!(room.0[0].len(), 3);
-        assert!(room.0[0].contains(&Vec3D::new(0, 0, 0)));
-        assert!(room.0[0].contains(&Vec3D::new(1, 1, 1)));
-        assert!(room.0[0].contains(&Vec3D::new(2, 2, 2)));
+        assert_eq!(room.boxes.component_count(), 6);
+
+        // The three closest pairs are distance 1 apart: (0,1), (1,3) and (2,4)
+        room.connect_closest(3);
+        assert_eq!(room.boxes.component_count(), 3);
+        assert_eq!(room.boxes.component_sizes(), vec![3, 2, 1]);
+        assert!(room.boxes.connected(0, 3));
+        assert!(room.boxes.connected(2, 4));
+        assert!(!room.boxes.connected(0, 2));
+        assert_eq!(room.score(), 6);
     }
 
     #[test]
-    fn test_merge_junction_box_preserves_order() {
-        // Test that vectors from 'from' box come before vectors from 'to' box
-        let vectors = vec![Vec3D::new(0, 0, 0), Vec3D::new(1, 1, 1)];
+    fn test_connect_closest_skips_already_connected() {
+        // The third closest pair (0,2) is already connected through 1
+        let vectors = vec![
+            Vec3D::new(0, 0, 0),
+            Vec3D::new(1, 0, 0),
+            Vec3D::new(2, 0, 0),
+            Vec3D::new(10, 0, 0),
+        ];
         let mut room = JunctionRoom::new(vectors);
-
-        // Merge box 0 into box 1
-        room.merge_junction_box(0, 1);
-
-        // The merged box should have vectors in order: from_box then to_box
-        assert_eq!(room.0.len(), 1);
-        assert_eq!(room.0[0].len(), 2);
-        assert_eq!(room.0[0][0], Vec3D::new(0, 0, 0)); // from box 0
-        assert_eq!(room.0[0][1], Vec3D::new(1, 1, 1)); // from box 1
+        room.connect_closest(3);
+        assert_eq!(room.boxes.component_sizes(), vec![3, 1]);
     }
 
     #[test]
-    fn test_merge_junction_box_consecutive_merges() {
-        // Test multiple consecutive merges
+    fn test_find_closest_pairs_matches_vectors() {
         let vectors = vec![
             Vec3D::new(0, 0, 0),
+            Vec3D::new(5, 0, 0),
             Vec3D::new(1, 0, 0),
-            Vec3D::new(2, 0, 0),
-            Vec3D::new(3, 0, 0),
         ];
-        let mut room = JunctionRoom::new(vectors);
-
-        assert_eq!(room.0.len(), 4);
-
-        // Merge 0 into 1
-        room.merge_junction_box(0, 1);
-        assert_eq!(room.0.len(), 3);
-        assert_eq!(room.0[0].len(), 2);
-
-        // Merge 0 (which was originally 1) into 1 (which was originally 2)
-        room.merge_junction_box(0, 1);
-        assert_eq!(room.0.len(), 2);
-        assert_eq!(room.0[0].len(), 3);
-
-        // Merge 1 (which was originally 3) into 0
-        room.merge_junction_box(1, 0);
-        assert_eq!(room.0.len(), 1);
-        assert_eq!(room.0[0].len(), 4);
+        assert_eq!(
+            find_closest_pairs(&vectors).collect::<Vec<_>>(),
+            vec![(0, 2), (1, 2), (0, 1)]
+        );
+    }
 
-        // All vectors should be in the final box
-        assert!(room.0[0].contains(&Vec3D::new(0, 0, 0)));
-        assert!(room.0[0].contains(&Vec3D::new(1, 0, 0)));
-        assert!(room.0[0].contains(&Vec3D::new(2, 0, 0)));
-        assert!(room.0[0].contains(&Vec3D::new(3, 0, 0)));
+    #[test]
+    fn test_generated_input() {
+        let input = Day08.generate(100, &mut Rng::new(2025));
+        assert!(Day08::part1(&input).is_ok());
+        assert!(Day08::part2(&input).is_ok());
     }
 }
@@ -1,6 +1,18 @@
 use std::str::FromStr;
 
-use crate::{solution::Solution, utils::vec2d::Vec2D};
+use crate::{
+    generator::Generator,
+    repl::Inspect,
+    solution::Solution,
+    utils::{
+        parse::{ParseError, parse_lines},
+        polygon::Polygon,
+        rect::Rect,
+        rng::Rng,
+        vec2d::Vec2D,
+        viz::{Color, Scene, Style, scale_to_fit},
+    },
+};
 use anyhow::Result;
 
 pub struct Day09;
@@ -15,145 +27,175 @@ impl Solution for Day09 {
     /// Part 1: Find the largest rectangle constructable by any two opposing red tiles.
     /// No restriction on whether the rectangle crosses polygon boundaries.
     fn part1(input: &str) -> Result<Self::Answer> {
-        let tile_floor = input.parse::<TileFloor>()?;
-        Ok(tile_floor.find_largest_rectangle_area().unwrap())
+        Self::part1_parsed(&Self::parse(input)?)
     }
 
     /// Part 2: Find the largest rectangle constructable by two opposing red tiles,
     /// but the entire rectangle area must be either red or green tiles
     /// (i.e., entirely enclosed within the polygon, not crossing any edges).
     fn part2(input: &str) -> Result<Self::Answer> {
+        Self::part2_parsed(&Self::parse(input)?)
+    }
+
+    /// Draws the polygon scaled down to fit, with the part 2 rectangle filled in.
+    fn visualize(input: &str) -> Result<Vec<Scene>> {
         let tile_floor = input.parse::<TileFloor>()?;
-        let (p1, p2) = tile_floor
+        let rectangle = tile_floor.find_non_intersecting_rectangle();
+        // The rectangle's corners lie within the tiles' bounds, so scaling them
+        // along with the tiles doesn't change the scale
+        let mut points = tile_floor.0.vertices().to_vec();
+        points.extend(rectangle.iter().flat_map(|r| [r.min(), r.max()]));
+        let mut scaled = scale_to_fit(&points, 200);
+
+        let mut scene = Scene::new();
+        if rectangle.is_some() {
+            let (max, min) = (scaled.pop().unwrap(), scaled.pop().unwrap());
+            for tile in Rect::new(min, max).points() {
+                scene.set(tile, Style::new('O', Color::BLUE));
+            }
+        }
+        for (i, tile) in scaled.iter().enumerate() {
+            let next = scaled[(i + 1) % scaled.len()];
+            scene.line(*tile, next, Style::new('X', Color::GREEN));
+        }
+        scene.draw(&scaled, Style::new('#', Color::RED));
+        Ok(vec![scene])
+    }
+}
+
+impl Inspect for Day09 {
+    type Parsed = TileFloor;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
+    }
+
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        Ok(parsed.find_largest_rectangle_area().unwrap())
+    }
+
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        let rectangle = parsed
             .find_non_intersecting_rectangle()
             .ok_or_else(|| anyhow::anyhow!("No non-intersecting rectangle found"))?;
-        Ok(square_area(&p1, &p2))
+        Ok(rectangle.area())
+    }
+
+    fn structures() -> &'static [&'static str] {
+        &["tiles", "polygon", "largest", "enclosed"]
+    }
+
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String> {
+        let format_rectangle =
+            |rectangle: Rect| format!("{} (area {})", rectangle, rectangle.area());
+        let dump = match structure {
+            "tiles" => parsed
+                .0
+                .vertices()
+                .iter()
+                .map(|tile| tile.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "polygon" => format!(
+                "{} vertices, area {}, {} tiles on the edges, {} inside",
+                parsed.0.vertices().len(),
+                parsed.0.area(),
+                parsed.0.boundary_points(),
+                parsed.0.interior_points()
+            ),
+            "largest" => format_rectangle(parsed.rectangles().into_iter().next()?),
+            "enclosed" => format_rectangle(parsed.find_non_intersecting_rectangle()?),
+            _ => return None,
+        };
+        Some(dump)
     }
 }
 
 /// Represents a tile floor with red tiles at vertices forming a polygon.
-/// - Red tiles: The Vec2D points in the list (vertices of the polygon)
+/// - Red tiles: The polygon's vertices
 /// - Green tiles: All tiles between consecutive red tiles (edges) and inside the polygon
-struct TileFloor(Vec<Vec2D>);
+pub struct TileFloor(Polygon);
 
 impl TileFloor {
     /// Part 1 solution: Find the largest rectangle area formed by any two red tiles.
     /// Simply computes all possible rectangles and returns the maximum area.
     fn find_largest_rectangle_area(&self) -> Option<u64> {
-        let all_pairs = self.rectangles();
-        all_pairs
-            .into_iter()
-            .map(|(a, b)| square_area(&a, &b))
-            .max()
+        self.rectangles().iter().map(Rect::area).max()
     }
 
-    /// Generate all possible pairs of red tiles and sort them by rectangle area (largest first).
-    /// Each pair represents opposing corners of a potential rectangle.
-    fn rectangles(&self) -> Vec<(Vec2D, Vec2D)> {
-        let mut all_pairs = vec![];
+    /// Generate all possible rectangles with two red tiles as opposing corners,
+    /// sorted by area (largest first).
+    fn rectangles(&self) -> Vec<Rect> {
+        let red_tiles = self.0.vertices();
+        let mut rectangles = vec![];
         // Generate all unique pairs of red tiles
-        for i in 0..self.0.len() {
-            for j in i + 1..self.0.len() {
-                all_pairs.push((self.0[i], self.0[j]));
+        for i in 0..red_tiles.len() {
+            for j in i + 1..red_tiles.len() {
+                rectangles.push(Rect::new(red_tiles[i], red_tiles[j]));
             }
         }
 
         // Sort by area in descending order (largest rectangles first)
-        all_pairs.sort_by(|p1, p2| square_area(&p1.0, &p1.1).cmp(&square_area(&p2.0, &p2.1)));
-        all_pairs.reverse();
-
-        all_pairs
-    }
-
-    /// Part 2 solution: Find the largest rectangle that doesn't cross any polygon edges.
-    /// Checks each rectangle (in descending area order) to see if it intersects with
-    /// any of the polygon's edges (formed by consecutive red tiles).
-    fn find_non_intersecting_rectangle(&self) -> Option<(Vec2D, Vec2D)> {
-        let length = self.0.len();
-        let rectangles = self.rectangles();
+        rectangles.sort_by_key(Rect::area);
+        rectangles.reverse();
 
         rectangles
-            .iter()
-            .find(|(p1, p2)| {
-                // Get the bounds of the rectangle formed by these two red tiles
-                let (xmin, xmax, ymin, ymax) = edges(p1, p2);
-
-                // Check if this rectangle crosses any edge of the polygon
-                for (i, red_tile) in self.0.iter().enumerate() {
-                    let next_red_tile = &self.0[(i + 1) % length];
-
-                    // Check if edge is vertical (same x-coordinate)
-                    if red_tile.x == next_red_tile.x {
-                        let (ylmin, ylmax) = (
-                            red_tile.y.min(next_red_tile.y),
-                            red_tile.y.max(next_red_tile.y),
-                        );
-                        // Check if rectangle crosses this vertical edge
-                        // Rectangle crosses if: edge's x is strictly between rectangle's x bounds
-                        // AND there's overlap in y coordinates
-                        if xmin < red_tile.x
-                            && xmax > red_tile.x
-                            && !(ymin >= ylmax || ymax <= ylmin)
-                        {
-                            return false; // Rectangle crosses an edge, invalid
-                        }
-                    }
-                    // Check if edge is horizontal (same y-coordinate)
-                    else if red_tile.y == next_red_tile.y {
-                        let (xlmin, xlmax) = (
-                            red_tile.x.min(next_red_tile.x),
-                            red_tile.x.max(next_red_tile.x),
-                        );
-                        // Check if rectangle crosses this horizontal edge
-                        // Rectangle crosses if: edge's y is strictly between rectangle's y bounds
-                        // AND there's overlap in x coordinates
-                        if ymin < red_tile.y
-                            && ymax > red_tile.y
-                            && !(xmin >= xlmax || xmax <= xlmin)
-                        {
-                            return false; // Rectangle crosses an edge, invalid
-                        }
-                    } else {
-                        // All edges should be either horizontal or vertical
-                        unreachable!()
-                    }
-                }
-
-                // Rectangle doesn't cross any edges, it's valid
-                true
-            })
-            .copied()
     }
-}
-
-/// Calculate the area of a rectangle formed by two opposing corner points.
-/// The area includes both corner tiles, hence the +1 for width and height.
-fn square_area(top_left: &Vec2D, bottom_right: &Vec2D) -> u64 {
-    let width = i64::abs(bottom_right.x - top_left.x) + 1;
-    let height = i64::abs(bottom_right.y - top_left.y) + 1;
-    width as u64 * height as u64
-}
 
-/// Extract the bounding box edges from two points.
-/// Returns (xmin, xmax, ymin, ymax) representing the rectangle boundaries.
-fn edges(p1: &Vec2D, p2: &Vec2D) -> (i64, i64, i64, i64) {
-    let xmin = p1.x.min(p2.x);
-    let xmax = p1.x.max(p2.x);
-    let ymin = p1.y.min(p2.y);
-    let ymax = p1.y.max(p2.y);
-    (xmin, xmax, ymin, ymax)
+    /// Part 2 solution: Find the largest rectangle made only of red and green tiles,
+    /// checking each rectangle (in descending area order) against the polygon.
+    fn find_non_intersecting_rectangle(&self) -> Option<Rect> {
+        self.rectangles()
+            .into_iter()
+            .find(|rectangle| self.0.contains_rect(rectangle))
+    }
 }
 
 /// Parse the input into a TileFloor.
-/// Each line represents a red tile position (vertex of the polygon).
+/// Each line represents a red tile position (vertex of the polygon), and
+/// together they must form a simple rectilinear loop.
 impl FromStr for TileFloor {
-    type Err = anyhow::Error;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let tiles = s
-            .lines()
-            .map(FromStr::from_str)
-            .collect::<Result<Vec<_>>>()?;
-        Ok(Self(tiles))
+        let tiles = parse_lines(s, Vec2D::from_str)?;
+        let polygon = Polygon::new(tiles).map_err(|err| {
+            // Each line is one vertex; without a vertex to blame, point past the end
+            let span = err
+                .vertex()
+                .and_then(|vertex| s.lines().nth(vertex))
+                .unwrap_or(&s[s.len()..]);
+            ParseError::at(s, span, err)
+        })?;
+        Ok(Self(polygon))
+    }
+}
+
+/// Generates a rectilinear "skyline" polygon made of `size` columns of random
+/// width and height standing on a common baseline, i.e. `2 * size + 2` red tiles.
+impl Generator for Day09 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let base = rng.range(0..1000);
+        let mut x = rng.range(0..1000);
+        let mut height = base;
+        let mut tiles = vec![Vec2D::new(x, base)];
+
+        for _ in 0..size.max(1) {
+            let mut next_height = height;
+            while next_height == height {
+                next_height = base + rng.range(1..100_000);
+            }
+            tiles.push(Vec2D::new(x, next_height));
+            x += rng.range(1..100_000 / size.max(1) as i64 + 2);
+            tiles.push(Vec2D::new(x, next_height));
+            height = next_height;
+        }
+        tiles.push(Vec2D::new(x, base));
+
+        tiles
+            .iter()
+            .map(|tile| format!("{},{}", tile.x(), tile.y()))
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
 
@@ -172,4 +214,28 @@ mod tests {
         let answer = Day09.run_test2();
         assert_eq!(answer, 24);
     }
+
+    #[test]
+    fn test_invalid_polygon_is_rejected() {
+        let err = "1,1\n5,1\n5,5\n3,7".parse::<TileFloor>().err().unwrap();
+        assert_eq!(err.message(), "Edge 2 is not axis-aligned");
+        assert_eq!((err.line, err.column), (3, 1));
+
+        let err = "0,0\n2,0\n2,2\n1,2\n1,-1\n0,-1"
+            .parse::<TileFloor>()
+            .err()
+            .unwrap();
+        assert_eq!(err.message(), "Edges 0 and 3 intersect");
+        assert_eq!(err.line, 4);
+
+        let err = "1,1\n5,1".parse::<TileFloor>().err().unwrap();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_generated_input() {
+        let input = Day09.generate(50, &mut Rng::new(2025));
+        assert!(Day09::part1(&input).is_ok());
+        assert!(Day09::part2(&input).is_ok());
+    }
 }
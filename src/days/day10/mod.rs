@@ -1,7 +1,16 @@
 use std::str::FromStr;
 
-use crate::solution::Solution;
+use crate::{
+    generator::Generator,
+    repl::Inspect,
+    solution::Solution,
+    utils::{
+        parse::{ParseError, bracketed, bracketed_list, chars, parse_lines},
+        rng::Rng,
+    },
+};
 use anyhow::Result;
+use itertools::Itertools;
 use rayon::prelude::*;
 
 pub struct Day10;
@@ -14,11 +23,23 @@ impl Solution for Day10 {
     }
 
     fn part1(input: &str) -> Result<Self::Answer> {
-        let machines = input
-            .lines()
-            .map(|line| Machine::from_str(line))
-            .collect::<Result<Vec<Machine>>>()?;
-        let minimal_button_presses = machines
+        Self::part1_parsed(&Self::parse(input)?)
+    }
+
+    fn part2(input: &str) -> Result<Self::Answer> {
+        Self::part2_parsed(&Self::parse(input)?)
+    }
+}
+
+impl Inspect for Day10 {
+    type Parsed = Vec<Machine>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(parse_lines(input, Machine::from_str)?)
+    }
+
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        let minimal_button_presses = parsed
             .iter()
             .map(|machine| machine.find_minimal_button_presses())
             .collect::<Option<Vec<usize>>>()
@@ -26,23 +47,48 @@ impl Solution for Day10 {
         Ok(minimal_button_presses.iter().sum::<usize>() as u64)
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let machines = input
-            .lines()
-            .map(|line| Machine::from_str(line))
-            .collect::<Result<Vec<Machine>>>()?;
-
-        let minimal_button_presses = machines
-            .into_par_iter()
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        let minimal_button_presses = parsed
+            .par_iter()
             .map(|machine| machine.find_minimal_button_presses_for_joltage_requirement())
             .collect::<Option<Vec<u64>>>()
             .ok_or_else(|| anyhow::anyhow!("No minimal button presses found"))?;
         Ok(minimal_button_presses.iter().sum::<u64>())
     }
+
+    fn structures() -> &'static [&'static str] {
+        &["machines", "presses"]
+    }
+
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String> {
+        let dump = match structure {
+            "machines" => parsed
+                .iter()
+                .map(|machine| {
+                    format!(
+                        "lights: {:#b}, buttons: {:?}, joltage: {:?}",
+                        machine.light_bit_pattern,
+                        machine
+                            .buttons
+                            .iter()
+                            .map(|button| format!("{:#b}", button))
+                            .collect::<Vec<_>>(),
+                        machine.joltage_requirements
+                    )
+                })
+                .join("\n"),
+            "presses" => parsed
+                .iter()
+                .map(|machine| format!("{:?}", machine.find_minimal_button_presses()))
+                .join("\n"),
+            _ => return None,
+        };
+        Some(dump)
+    }
 }
 
 #[derive(Debug)]
-struct Machine {
+pub struct Machine {
     light_bit_pattern: u16,
     buttons: Vec<u16>,
     joltage_requirements: Vec<u16>,
@@ -51,18 +97,24 @@ struct Machine {
 // ^^^^^^ ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ ^^^^^^^^^
 // light  buttons                         joltage_requirements
 impl FromStr for Machine {
-    type Err = anyhow::Error;
+    type Err = ParseError;
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         let parts = s.split_whitespace().collect::<Vec<&str>>();
-        let light_bit_pattern = parse_light_bit_pattern(parts[0])
-            .ok_or(anyhow::anyhow!("Invalid light bit pattern: {}", parts[0]))?;
-        let buttons = parts[1..parts.len() - 1]
+        let [light_part, button_parts @ .., joltage_part] = parts.as_slice() else {
+            return Err(ParseError::at(
+                s,
+                s,
+                "Invalid machine, expected '[lights] (buttons)... {joltage}'",
+            ));
+        };
+        let light_bit_pattern =
+            parse_light_bit_pattern(light_part).map_err(|err| err.within(s, light_part))?;
+        let buttons = button_parts
             .iter()
-            .map(|part| parse_button(part).ok_or(anyhow::anyhow!("Invalid button: {}", part)))
-            .collect::<Result<Vec<u16>>>()?;
-        let joltage_requirements = parse_joltage_requirements(parts[parts.len() - 1]).ok_or(
-            anyhow::anyhow!("Invalid joltage requirements: {}", parts[parts.len() - 1]),
-        )?;
+            .map(|part| parse_button(part).map_err(|err| err.within(s, part)))
+            .collect::<std::result::Result<Vec<u16>, _>>()?;
+        let joltage_requirements = bracketed_list::<u16>(joltage_part, '{', '}')
+            .map_err(|err| err.within(s, joltage_part))?;
         Ok(Self {
             light_bit_pattern,
             buttons,
@@ -145,11 +197,7 @@ impl Machine {
         }
 
         // Minimize the total number of button presses
-        let total: z3::ast::Int = button_vars
-            .iter()
-            .map(|v| v.clone())
-            .reduce(|a, b| a + b)
-            .unwrap();
+        let total: z3::ast::Int = button_vars.iter().cloned().reduce(|a, b| a + b).unwrap();
         opt.minimize(&total);
 
         // Solve
@@ -163,35 +211,78 @@ impl Machine {
     }
 }
 
-fn parse_light_bit_pattern(s: &str) -> Option<u16> {
-    let clean_str = s.strip_prefix('[')?.strip_suffix(']')?;
-    Some(clean_str.chars().enumerate().fold(
-        0,
-        |acc, (i, c)| {
-            if c == '#' { acc | (1 << i) } else { acc }
-        },
-    ))
+fn parse_light_bit_pattern(s: &str) -> std::result::Result<u16, ParseError> {
+    let inner = bracketed(s, '[', ']')?;
+    let lights = chars(inner, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+    .map_err(|err| err.within(s, inner))?;
+    if lights.len() > 16 {
+        return Err(ParseError::at(s, s, "At most 16 lights are supported"));
+    }
+    Ok(lights
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (i, &on)| if on { acc | (1 << i) } else { acc }))
 }
 
 // (1,2,3) -> 0b111
-fn parse_button(s: &str) -> Option<u16> {
-    let clean_str = s.strip_prefix('(')?.strip_suffix(')')?;
-    let parts = clean_str
-        .split(',')
-        .map(|part| Ok(part.parse::<u16>()?))
-        .collect::<Result<Vec<u16>>>()
-        .ok()?;
-    Some(parts.iter().fold(0, |acc, part| acc | (1 << part)))
+fn parse_button(s: &str) -> std::result::Result<u16, ParseError> {
+    bracketed_list::<u32>(s, '(', ')')?
+        .iter()
+        .try_fold(0, |acc, &light| Some(acc | 1u16.checked_shl(light)?))
+        .ok_or_else(|| ParseError::at(s, s, "Buttons can only wire lights 0 to 15"))
 }
+/// Generates `size` machines. Each light pattern is the result of a random set of
+/// button presses and each joltage requirement the result of random press counts,
+/// so every generated machine is solvable.
+impl Generator for Day10 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                let lights = rng.range_usize(3..=10);
+                let buttons = (0..rng.range_usize(2..=12))
+                    .map(|_| {
+                        let mut button =
+                            (0..lights).filter(|_| rng.chance(0.4)).collect::<Vec<_>>();
+                        if button.is_empty() {
+                            button.push(rng.range_usize(..lights));
+                        }
+                        button
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut pattern = vec![false; lights];
+                let mut joltage = vec![0; lights];
+                for button in &buttons {
+                    let toggled = rng.chance(0.5);
+                    let presses = rng.range(0..=20);
+                    for &light in button {
+                        pattern[light] ^= toggled;
+                        joltage[light] += presses;
+                    }
+                }
 
-// {3,5,4,7} -> vec![3, 5, 4, 7]
-fn parse_joltage_requirements(s: &str) -> Option<Vec<u16>> {
-    let clean_str = s.strip_prefix('{')?.strip_suffix('}')?;
-    clean_str
-        .split(',')
-        .map(|p| p.parse::<u16>().ok())
-        .collect()
+                format!(
+                    "[{}] {} {{{}}}",
+                    pattern
+                        .iter()
+                        .map(|&on| if on { '#' } else { '.' })
+                        .collect::<String>(),
+                    buttons
+                        .iter()
+                        .map(|button| format!("({})", button.iter().join(",")))
+                        .join(" "),
+                    joltage.iter().join(",")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,4 +298,23 @@ mod tests {
         let answer = Day10.run_test2();
         assert_eq!(answer, 33);
     }
+
+    #[test]
+    fn test_generated_input() {
+        let input = Day10.generate(50, &mut Rng::new(2025));
+        assert!(Day10::part1(&input).is_ok());
+        // Part 2 hands every machine to the solver, which is too slow for a unit test
+    }
+
+    #[test]
+    fn test_parse_error_on_truncated_machine() {
+        let err = Machine::from_str("[.##.]").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+
+        let err = Machine::from_str("[.##.] (3) (1,x) {3,5,4,7}").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 15));
+
+        let err = Machine::from_str("[.#x.] (3) {3}").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+    }
 }
@@ -1,86 +1,175 @@
-use pathfinding::prelude::count_paths;
-use std::{collections::HashMap, str::FromStr};
-
-use crate::solution::Solution;
+use std::collections::HashSet;
+
+use crate::{
+    generator::Generator,
+    repl::Inspect,
+    solution::Solution,
+    utils::{
+        big_uint::{BigUint, Count, Overflow},
+        graph::{Graph, PathCountError},
+        memo::Memo,
+        rng::Rng,
+    },
+};
 use anyhow::Result;
+use itertools::Itertools;
 
 pub struct Day11;
 
 impl Solution for Day11 {
-    type Answer = u64;
+    type Answer = BigUint;
 
     fn day(&self) -> u8 {
         11
     }
 
     fn part1(input: &str) -> Result<Self::Answer> {
-        let graph = Graph::from_str(input)?;
-        let paths = graph.count_paths("you", "out")?;
-        Ok(paths as u64)
+        Self::part1_parsed(&Self::parse(input)?)
     }
 
     fn part2(input: &str) -> Result<Self::Answer> {
-        let graph = Graph::from_str(input)?;
+        Self::part2_parsed(&Self::parse(input)?)
+    }
+}
 
-        let dac_to_out = graph.count_paths("dac", "out")?;
-        let fft_to_out = graph.count_paths("fft", "out")?;
+impl Inspect for Day11 {
+    type Parsed = Graph;
 
-        let dac_to_fft = graph.count_paths("dac", "fft")?;
-        let fft_to_dac = graph.count_paths("fft", "dac")?;
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
+    }
 
-        let svr_to_fft = graph.count_paths("svr", "fft")?;
-        let svr_to_dac = graph.count_paths("svr", "dac")?;
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        count_paths(parsed, "you", "out")
+    }
+
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        let dac_to_out = count_paths(parsed, "dac", "out")?;
+        let fft_to_out = count_paths(parsed, "fft", "out")?;
+
+        let dac_to_fft = count_paths(parsed, "dac", "fft")?;
+        let fft_to_dac = count_paths(parsed, "fft", "dac")?;
+
+        let svr_to_fft = count_paths(parsed, "svr", "fft")?;
+        let svr_to_dac = count_paths(parsed, "svr", "dac")?;
 
         let svr_to_out_via_dac_and_fft = svr_to_dac * dac_to_fft * fft_to_out;
         let svr_to_out_via_fft_and_dac = svr_to_fft * fft_to_dac * dac_to_out;
 
-        let paths = (svr_to_out_via_dac_and_fft + svr_to_out_via_fft_and_dac) as u64;
+        Ok(svr_to_out_via_dac_and_fft + svr_to_out_via_fft_and_dac)
+    }
 
-        Ok(paths)
+    fn structures() -> &'static [&'static str] {
+        &["adjacency", "order"]
+    }
+
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String> {
+        let dump = match structure {
+            "adjacency" => parsed.to_string().lines().sorted().join("\n"),
+            "order" => match parsed.topological_sort() {
+                Ok(order) => order.iter().map(|&id| parsed.label(id)).join(" "),
+                Err(cycle) => cycle.to_string(),
+            },
+            _ => return None,
+        };
+        Some(dump)
     }
 }
 
-struct Graph(HashMap<String, Vec<String>>);
-
-// aaa: bbb, ccc
-// ...
-impl FromStr for Graph {
-    type Err = anyhow::Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut graph = HashMap::new();
-        for line in s.lines() {
-            let (key, values) = line
-                .split_once(':')
-                .ok_or(anyhow::anyhow!("Invalid line: {}", line))?;
-            let key = key.trim().to_string();
-            let values = values
-                .trim()
-                .split(' ')
-                .map(|v| v.trim().to_string())
-                .collect();
-            graph.insert(key, values);
+/// Number of paths between two devices, treating unknown devices as dead ends.
+/// Counts in `u64` and only recounts with big integers if that overflows.
+fn count_paths(graph: &Graph, start: &str, end: &str) -> Result<BigUint> {
+    match count_paths_as::<u64>(graph, start, end) {
+        Ok(paths) => Ok(BigUint::from(paths)),
+        Err(PathCountError::Overflow) => {
+            log::debug!("Paths from {} to {} overflow a u64", start, end);
+            Ok(count_paths_as(graph, start, end)?)
         }
-        Ok(Graph(graph))
+        Err(err) => Err(err.into()),
     }
 }
 
-impl Graph {
-    fn count_paths(&self, start: &str, end: &str) -> Result<usize> {
-        let start = start.to_string();
-        let end = end.to_string();
-        let paths = count_paths(
-            start.clone(),
-            |node| {
-                self.0
-                    .get(node)
-                    .unwrap_or(&Vec::new())
-                    .iter()
-                    .cloned()
-                    .collect::<Vec<_>>()
-            },
-            |node| node == &end,
-        );
-        Ok(paths)
+/// Counts the paths recursively: a device has one path if it is the end, and
+/// otherwise as many as all of its outputs together, memoized per device.
+fn count_paths_as<C: Count>(graph: &Graph, start: &str, end: &str) -> Result<C, PathCountError> {
+    let (Some(start_id), Some(end_id)) = (graph.id(start), graph.id(end)) else {
+        return Ok(if start == end { C::one() } else { C::zero() });
+    };
+    // The recursion would never end on a cycle, but only the devices reachable
+    // from the start are ever asked for
+    let order = graph.reachable_postorder(start_id)?;
+    let mut paths = Memo::new(
+        |paths: &mut dyn FnMut(u32) -> Result<C, Overflow>, device: u32| {
+            if device == end_id {
+                return Ok(C::one());
+            }
+            graph
+                .successors(device)
+                .iter()
+                .try_fold(C::zero(), |total, &next| total.try_add(&paths(next)?))
+        },
+    );
+    // Asking for every device after its outputs keeps the recursion shallow
+    // however long the paths get
+    for device in order {
+        paths.get(device)?;
+    }
+    paths.log_stats(&format!("Paths from {} to {}", start, end));
+    Ok(paths.get(start_id)?)
+}
+
+/// Generates a DAG of `size` devices (at least the 5 named ones the puzzle needs).
+/// Devices are laid out in a random topological order and only connect to one of
+/// the next few devices, which keeps paths long and the named devices reachable.
+/// Names have three letters like the puzzle's, or more when `size` needs them.
+impl Generator for Day11 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        const WINDOW: usize = 8;
+        let named = ["svr", "you", "fft", "dac", "out"];
+        let mut names = named.map(String::from).into_iter().collect::<HashSet<_>>();
+        // Keep at most half the names taken, so that drawing a free one stays quick
+        let mut name_len = 3;
+        while 26usize.saturating_pow(name_len as u32) < 2 * size {
+            name_len += 1;
+        }
+        let mut devices = Vec::new();
+        while devices.len() + named.len() < size {
+            let name = (0..name_len)
+                .map(|_| char::from(b'a' + rng.range(0..26) as u8))
+                .collect::<String>();
+            if names.insert(name.clone()) {
+                devices.push(name);
+            }
+        }
+
+        // svr always comes first and out last, the others are placed at random in between
+        let mut order = vec!["svr".to_string()];
+        order.append(&mut devices);
+        for name in ["you", "fft", "dac"] {
+            let position = rng.range_usize(1..=order.len());
+            order.insert(position, name.to_string());
+        }
+        order.push("out".to_string());
+
+        let mut lines = (0..order.len() - 1)
+            .map(|i| {
+                let window = i + 1..(i + 1 + WINDOW).min(order.len());
+                let mut outputs = window
+                    .clone()
+                    .filter(|_| rng.chance(0.3))
+                    .collect::<Vec<_>>();
+                if outputs.is_empty() {
+                    outputs.push(rng.range_usize(window));
+                }
+                format!(
+                    "{}: {}",
+                    order[i],
+                    outputs.iter().map(|&j| order[j].as_str()).join(" ")
+                )
+            })
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut lines);
+        lines.join("\n")
     }
 }
 
@@ -91,12 +180,47 @@ mod tests {
     #[test]
     fn test_part1() {
         let answer = Day11.run_test1();
-        assert_eq!(answer, 5);
+        assert_eq!(answer, 5u64);
     }
 
     #[test]
     fn test_part2() {
         let answer = Day11.run_test2();
-        assert_eq!(answer, 2);
+        assert_eq!(answer, 2u64);
+    }
+
+    #[test]
+    fn test_counts_beyond_u64_are_promoted() {
+        // 70 diamonds in a row between you and out, 2^70 paths
+        let mut lines = vec!["you: a0".to_string()];
+        for i in 0..70 {
+            lines.push(format!("a{i}: l{i} r{i}"));
+            lines.push(format!("l{i}: a{}", i + 1));
+            lines.push(format!("r{i}: a{}", i + 1));
+        }
+        lines.push("a70: out".to_string());
+        let answer = Day11::part1(&lines.join("\n")).unwrap();
+        assert_eq!(answer.to_string(), "1180591620717411303424");
+    }
+
+    #[test]
+    fn test_unreachable_cycles_are_ignored() {
+        let input = "you: out\nzzz: yyy\nyyy: zzz";
+        assert_eq!(Day11::part1(input).unwrap(), 1u64);
+        assert!(Day11::part1("you: aaa\naaa: you out").is_err());
+    }
+
+    #[test]
+    fn test_generated_input_outgrows_three_letter_names() {
+        let input = Day11.generate(20_000, &mut Rng::new(11));
+        assert_eq!(input.lines().count(), 19_999);
+        assert!(Day11::part1(&input).is_ok());
+    }
+
+    #[test]
+    fn test_generated_input() {
+        let input = Day11.generate(30, &mut Rng::new(2025));
+        assert!(Day11::part1(&input).is_ok());
+        assert!(Day11::part2(&input).is_ok());
     }
 }
@@ -1,5 +1,15 @@
-use crate::solution::Solution;
-use anyhow::{Result, anyhow};
+use crate::{
+    generator::Generator,
+    repl::Inspect,
+    solution::Solution,
+    utils::{
+        parse::{
+            ParseError, exactly, key_value, parse_at, parse_lines, sections, split_pair, words,
+        },
+        rng::Rng,
+    },
+};
+use anyhow::Result;
 use std::str::FromStr;
 
 pub struct Day12;
@@ -12,34 +22,70 @@ impl Solution for Day12 {
     }
 
     fn part1(input: &str) -> Result<Self::Answer> {
-        // I
-        let present_list = input
-            .split("\n\n")
-            .last()
-            .ok_or(anyhow::anyhow!("No present list"))?;
-
-        // Am
-        let present_grids = present_list
-            .lines()
-            .map(|line| PresentGrid::from_str(line))
-            .collect::<Result<Vec<PresentGrid>>>()?;
+        Self::part1_parsed(&Self::parse(input)?)
+    }
+
+    fn part2(_: &str) -> Result<Self::Answer> {
+        Ok(0)
+    }
+}
+
+impl Inspect for Day12 {
+    type Parsed = Vec<PresentGrid>;
 
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_present_grids(input)
+    }
+
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer> {
+        // I Am
         // Annoyed.
-        let simple_fit_count = present_grids
-            .iter()
-            .filter(|grid| grid.simple_fit())
-            .count();
+        let simple_fit_count = parsed.iter().filter(|grid| grid.simple_fit()).count();
 
         // Done.
         Ok(simple_fit_count as u64)
     }
 
-    fn part2(_: &str) -> Result<Self::Answer> {
+    fn part2_parsed(_: &Self::Parsed) -> Result<Self::Answer> {
         Ok(0)
     }
+
+    fn structures() -> &'static [&'static str] {
+        &["regions"]
+    }
+
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String> {
+        let dump = match structure {
+            "regions" => parsed
+                .iter()
+                .map(|grid| {
+                    format!(
+                        "{}x{}: {:?} fits: {}",
+                        grid.width,
+                        grid.height,
+                        grid.presents,
+                        grid.simple_fit()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => return None,
+        };
+        Some(dump)
+    }
+}
+
+/// Parses the regions under the tree, which are listed after the present shapes
+fn parse_present_grids(input: &str) -> Result<Vec<PresentGrid>> {
+    let present_list = sections(input)
+        .last()
+        .ok_or(anyhow::anyhow!("No present list"))?;
+
+    Ok(parse_lines(present_list, PresentGrid::from_str)
+        .map_err(|err| err.within(input, present_list))?)
 }
 
-struct PresentGrid {
+pub struct PresentGrid {
     width: u32,
     height: u32,
     presents: [u32; 6],
@@ -54,30 +100,19 @@ impl PresentGrid {
 }
 
 impl FromStr for PresentGrid {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Parse format: "12x5: 1 0 1 0 3 2"
-        let (dimensions, presents) = s
-            .split_once(": ")
-            .ok_or(anyhow!("Invalid format: expected 'WxH: p1 p2 p3 p4 p5 p6'"))?;
-
-        let (width, height) = dimensions
-            .split_once('x')
-            .ok_or(anyhow!("Invalid dimensions format: expected 'WxH'"))?;
-
-        let width = width.parse::<u32>()?;
-        let height = height.parse::<u32>()?;
+        let (dimensions, presents) = key_value(s)?;
+        let (width, height) =
+            split_pair(dimensions, "x", "WxH").map_err(|err| err.within(s, dimensions))?;
+        let width = parse_at::<u32>(s, width)?;
+        let height = parse_at::<u32>(s, height)?;
 
         // Parse presents (e.g., "1 0 1 0 3 2")
-        let presents = presents
-            .split_whitespace()
-            .map(|s| s.parse::<u32>())
-            .collect::<Result<Vec<u32>, _>>()?;
-
-        let presents = presents
-            .try_into()
-            .map_err(|_| anyhow!("Invalid number of presents"))?;
+        let presents =
+            exactly(presents, words::<u32>(presents)?).map_err(|err| err.within(s, presents))?;
 
         Ok(PresentGrid {
             width,
@@ -87,6 +122,52 @@ impl FromStr for PresentGrid {
     }
 }
 
+/// Generates the 6 present shapes followed by `size` regions. Each region asks for
+/// somewhere between none and a bit more presents than would trivially fit.
+impl Generator for Day12 {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let shapes = (0..6)
+            .map(|i| {
+                let rows = (0..3)
+                    .map(|_| {
+                        (0..3)
+                            .map(|_| if rng.chance(0.7) { '#' } else { '.' })
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<_>>();
+                format!("{}:\n{}", i, rows.join("\n"))
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let regions = (0..size)
+            .map(|_| {
+                let width = rng.range(4..=50);
+                let height = rng.range(4..=50);
+                let capacity = (width / 3 * height / 3) as f64;
+                let total = rng.range(0..=(capacity * 1.2) as i64);
+                let mut presents = [0; 6];
+                for _ in 0..total {
+                    presents[rng.range_usize(..6)] += 1;
+                }
+                format!(
+                    "{}x{}: {}",
+                    width,
+                    height,
+                    presents
+                        .iter()
+                        .map(|p| p.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!("{}\n\n{}", shapes, regions)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +183,11 @@ mod tests {
         let answer = Day12.run_test2();
         assert_eq!(answer, 0); // TODO: Update with expected answer
     }
+
+    #[test]
+    fn test_generated_input() {
+        let input = Day12.generate(50, &mut Rng::new(2025));
+        assert!(Day12::part1(&input).is_ok());
+        assert!(Day12::part2(&input).is_ok());
+    }
 }
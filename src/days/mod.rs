@@ -10,3 +10,69 @@ pub mod day09;
 pub mod day10;
 pub mod day11;
 pub mod day12;
+
+/// Every day registered in [`with_day`]
+pub const DAYS: std::ops::RangeInclusive<u8> = 1..=12;
+
+/// Evaluates `$body` with `$solution` bound to the solution of day `$day`,
+/// bailing out of the enclosing function for days that don't exist.
+///
+/// ```ignore
+/// with_day!(day, solution => solution.run())
+/// ```
+macro_rules! with_day {
+    ($day:expr, $solution:ident => $body:expr) => {
+        match $day {
+            1 => {
+                let $solution = $crate::days::day01::Day01;
+                $body
+            }
+            2 => {
+                let $solution = $crate::days::day02::Day02;
+                $body
+            }
+            3 => {
+                let $solution = $crate::days::day03::Day03;
+                $body
+            }
+            4 => {
+                let $solution = $crate::days::day04::Day04;
+                $body
+            }
+            5 => {
+                let $solution = $crate::days::day05::Day05;
+                $body
+            }
+            6 => {
+                let $solution = $crate::days::day06::Day06;
+                $body
+            }
+            7 => {
+                let $solution = $crate::days::day07::Day07;
+                $body
+            }
+            8 => {
+                let $solution = $crate::days::day08::Day08;
+                $body
+            }
+            9 => {
+                let $solution = $crate::days::day09::Day09;
+                $body
+            }
+            10 => {
+                let $solution = $crate::days::day10::Day10;
+                $body
+            }
+            11 => {
+                let $solution = $crate::days::day11::Day11;
+                $body
+            }
+            12 => {
+                let $solution = $crate::days::day12::Day12;
+                $body
+            }
+            day => anyhow::bail!("Day {} not implemented", day),
+        }
+    };
+}
+pub(crate) use with_day;
@@ -0,0 +1,10 @@
+use crate::utils::rng::Rng;
+
+/// Produces random, syntactically valid puzzle inputs for a day.
+///
+/// `size` is the number of "primary" elements to generate (lines, ranges,
+/// points, ...), or the side length for grid based days. Each implementation
+/// documents how it interprets it.
+pub trait Generator {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String;
+}
@@ -0,0 +1,83 @@
+/// Normalizes a raw puzzle input so parsers can rely on a single, predictable shape,
+/// regardless of the editor or OS the file was saved with:
+///
+/// - a leading UTF-8 byte order mark is removed
+/// - `\r\n` and lone `\r` line endings become `\n`
+/// - trailing spaces and tabs are removed from every line
+/// - trailing blank lines are removed, and the result never ends with a newline
+///
+/// Leading whitespace and blank lines inside the input (e.g. section separators)
+/// are preserved.
+pub fn normalize(raw: &str) -> String {
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let unified = raw.replace("\r\n", "\n").replace('\r', "\n");
+    let lines = unified
+        .split('\n')
+        .map(|line| line.trim_end_matches([' ', '\t']))
+        .collect::<Vec<_>>();
+    lines.join("\n").trim_end_matches('\n').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::{
+        days::{day02::Day02, day05::Day05, day06::Day06},
+        solution::Solution,
+    };
+
+    /// Re-saves a sample the way a Windows editor would
+    fn windows_style(path: &str) -> String {
+        let sample = fs::read_to_string(path).unwrap();
+        format!("\u{feff}{}\r\n\r\n", sample.replace('\n', "  \r\n"))
+    }
+
+    #[test]
+    fn test_normalize_strips_bom() {
+        assert_eq!(normalize("\u{feff}L68\nR48"), "L68\nR48");
+    }
+
+    #[test]
+    fn test_normalize_converts_line_endings() {
+        assert_eq!(normalize("3-5\r\n10-14\r\n\r\n1\r5"), "3-5\n10-14\n\n1\n5");
+    }
+
+    #[test]
+    fn test_normalize_strips_trailing_newlines() {
+        assert_eq!(normalize("11-22,95-115\n"), "11-22,95-115");
+        assert_eq!(normalize("11-22,95-115\n\n\n"), "11-22,95-115");
+        assert_eq!(normalize("11-22,95-115"), "11-22,95-115");
+    }
+
+    #[test]
+    fn test_normalize_strips_trailing_whitespace() {
+        assert_eq!(normalize("123 328 \n*   +  \t\n  \n"), "123 328\n*   +");
+    }
+
+    #[test]
+    fn test_normalize_keeps_leading_whitespace_and_sections() {
+        assert_eq!(normalize("  6 98\n\n0:\n###"), "  6 98\n\n0:\n###");
+    }
+
+    #[test]
+    fn test_normalize_empty() {
+        assert_eq!(normalize(""), "");
+        assert_eq!(normalize("\u{feff}\r\n"), "");
+    }
+
+    #[test]
+    fn test_windows_style_samples_solve() {
+        let input = normalize(&windows_style("./src/days/day02/sample.txt"));
+        assert_eq!(Day02::part1(&input).unwrap(), 1227775554);
+
+        let input = normalize(&windows_style("./src/days/day05/sample.txt"));
+        assert_eq!(Day05::part1(&input).unwrap(), 3);
+        assert_eq!(Day05::part2(&input).unwrap(), 14);
+
+        let input = normalize(&windows_style("./src/days/day06/sample.txt"));
+        assert_eq!(Day06::part1(&input).unwrap(), 4277556);
+        assert_eq!(Day06::part2(&input).unwrap(), 3263827);
+    }
+}
@@ -1,14 +1,37 @@
-use crate::cli::Cli;
+use crate::cli::{Cli, Command};
 
+mod alloc;
 mod cli;
 mod days;
+mod generator;
+mod input;
+mod params;
+mod repl;
+mod report;
+mod sandbox;
+mod server;
 mod solution;
 mod utils;
 
-use anyhow::Result;
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Result, bail};
 use clap::Parser;
-use days::*;
-use solution::Solution;
+use days::with_day;
+use generator::Generator;
+use solution::{Solution, input_path, read_input};
+use utils::{
+    rng::Rng,
+    viz::{self, Format},
+};
+
+#[global_allocator]
+static ALLOCATOR: alloc::TrackingAllocator = alloc::TrackingAllocator;
+
 fn main() -> Result<()> {
     let log_level = if cfg!(test) || cfg!(not(debug_assertions)) {
         log::LevelFilter::Error
@@ -19,20 +42,95 @@ fn main() -> Result<()> {
         .filter_level(log_level)
         .init();
     let cli = Cli::parse();
-    let result = match cli.day {
-        1 => day01::Day01.run(),
-        2 => day02::Day02.run(),
-        3 => day03::Day03.run(),
-        4 => day04::Day04.run(),
-        5 => day05::Day05.run(),
-        6 => day06::Day06.run(),
-        7 => day07::Day07.run(),
-        8 => day08::Day08.run(),
-        9 => day09::Day09.run(),
-        10 => day10::Day10.run(),
-        11 => day11::Day11.run(),
-        12 => day12::Day12.run(),
-        _ => anyhow::bail!("Day {} not implemented", cli.day),
-    };
-    result
+    match cli.command {
+        Some(Command::Gen {
+            day,
+            size,
+            seed,
+            output,
+        }) => generate(day, size, seed, output),
+        Some(Command::Repl { day, sample }) => {
+            with_day!(day, solution => repl::start(solution, sample))
+        }
+        Some(Command::Report {
+            output,
+            sample,
+            sandbox,
+            cpu_seconds,
+            memory_mb,
+            wall_seconds,
+        }) => {
+            let limits = sandbox.then_some(sandbox::Limits {
+                cpu_seconds,
+                memory_mb,
+                wall_seconds,
+            });
+            report::generate(&output, sample, limits)
+        }
+        Some(Command::SandboxChild {
+            day,
+            input,
+            part2_input,
+        }) => {
+            let part2_input = part2_input.as_deref().unwrap_or(&input);
+            with_day!(day, solution => sandbox::child(solution, [&input, part2_input]))
+        }
+        Some(Command::Serve { port }) => server::serve(port),
+        None => run(
+            cli.day.ok_or_else(|| anyhow::anyhow!("No day given"))?,
+            cli.visualize.map(Format::from),
+        ),
+    }
+}
+
+fn run(day: u8, visualize: Option<Format>) -> Result<()> {
+    with_day!(day, solution => {
+        solution.run()?;
+        match visualize {
+            Some(format) => render_visualization(solution, format),
+            None => Ok(()),
+        }
+    })
+}
+
+fn render_visualization<T: Solution>(solution: T, format: Format) -> Result<()> {
+    let day = solution.day();
+    let input = read_input(&input_path(day))?;
+    let frames = T::visualize(&input)?;
+    if frames.is_empty() {
+        bail!("Day {} has no visualization", day);
+    }
+    match format {
+        Format::Ansi => viz::play(&frames, Duration::from_millis(100)),
+        _ => {
+            let dir = PathBuf::from(format!("viz/day{:02}", day));
+            viz::save_frames(&frames, format, &dir)?;
+            println!("Wrote {} frames to {}", frames.len(), dir.display());
+            Ok(())
+        }
+    }
+}
+
+fn generate(day: u8, size: usize, seed: Option<u64>, output: Option<PathBuf>) -> Result<()> {
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+    log::info!(
+        "Generating day {} input of size {} with seed {}",
+        day,
+        size,
+        seed
+    );
+
+    let mut rng = Rng::new(seed);
+    let input = with_day!(day, solution => solution.generate(size, &mut rng));
+
+    match output {
+        Some(path) => fs::write(path, input)?,
+        None => println!("{}", input),
+    }
+    Ok(())
 }
@@ -0,0 +1,62 @@
+use std::{cell::RefCell, collections::BTreeMap, fmt::Display, str::FromStr};
+
+// Parameters are per thread so that concurrently running tests (or REPL sessions)
+// never observe each other's overrides.
+thread_local! {
+    static PARAMS: RefCell<BTreeMap<String, String>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// A tunable parameter a day reads through [`get_or`], with a check that a value
+/// parses as the type the day reads it as.
+pub struct Parameter {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub check: fn(&str) -> Result<(), String>,
+}
+
+impl Parameter {
+    pub const fn new<T: FromStr>(name: &'static str, description: &'static str) -> Self
+    where
+        T::Err: Display,
+    {
+        Self {
+            name,
+            description,
+            check: |value| value.parse::<T>().map(drop).map_err(|err| err.to_string()),
+        }
+    }
+}
+
+/// Overrides a tunable solution parameter, e.g. the number of connections on Day08.
+pub fn set(name: &str, value: &str) {
+    PARAMS.with_borrow_mut(|params| params.insert(name.to_string(), value.to_string()));
+}
+
+pub fn unset(name: &str) {
+    PARAMS.with_borrow_mut(|params| params.remove(name));
+}
+
+pub fn clear() {
+    PARAMS.with_borrow_mut(|params| params.clear());
+}
+
+/// All currently overridden parameters
+pub fn all() -> Vec<(String, String)> {
+    PARAMS.with_borrow(|params| {
+        params
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    })
+}
+
+/// Reads a parameter, falling back to `default` when it is unset or can't be parsed.
+pub fn get_or<T: FromStr>(name: &str, default: T) -> T {
+    PARAMS.with_borrow(|params| match params.get(name) {
+        Some(value) => value.parse().unwrap_or_else(|_| {
+            log::warn!("Ignoring invalid value for parameter {}: {}", name, value);
+            default
+        }),
+        None => default,
+    })
+}
@@ -0,0 +1,257 @@
+use std::{
+    fmt::{Display, Formatter},
+    io::{self, BufRead, Write},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, bail};
+
+use crate::{
+    params::{self, Parameter},
+    solution::{Solution, input_path, read_input, report_parse_error, sample_path},
+};
+
+/// Lets the REPL look inside a day: parse its input once and dump the
+/// intermediate structures the solution works with.
+pub trait Inspect: Solution {
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+
+    /// Solves part 1 on an input that is already parsed, as `Solution::part1`
+    /// does after parsing
+    fn part1_parsed(parsed: &Self::Parsed) -> Result<Self::Answer>;
+
+    /// Solves part 2 on an input that is already parsed, as `Solution::part2`
+    /// does after parsing
+    fn part2_parsed(parsed: &Self::Parsed) -> Result<Self::Answer>;
+
+    /// Names of the structures [`Inspect::dump`] knows about
+    fn structures() -> &'static [&'static str];
+
+    fn dump(parsed: &Self::Parsed, structure: &str) -> Option<String>;
+
+    /// Tunable parameters the day reads through [`params`]
+    fn parameters() -> &'static [Parameter] {
+        &[]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Source {
+    Input,
+    Sample(u8),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Input => write!(f, "input"),
+            Source::Sample(part) => write!(f, "sample (part {})", part),
+        }
+    }
+}
+
+enum Action {
+    Print(String),
+    Quit,
+}
+
+const HELP: &str = "\
+Commands:
+  1, part1             run part 1 on the parsed input
+  2, part2             run part 2 on the parsed input
+  dump [structure]     print an intermediate structure, or list them
+  set [name value]     set a parameter, or list them
+  unset <name>         reset a parameter to its default
+  input                switch to the puzzle input
+  sample [part]        switch to the sample (optionally the part specific one)
+  reload               reload and reparse the current source
+  help                 show this message
+  quit, exit           leave the REPL";
+
+/// The loaded source of a REPL session. The input is read and parsed once per
+/// load, and both the parts and `dump` run on that parsed input, so the part
+/// timings leave parsing out. Loading reports how long parsing took instead.
+struct Session<T: Inspect> {
+    solution: T,
+    source: Source,
+    input: String,
+    parsed: T::Parsed,
+    parse_time: Duration,
+}
+
+impl<T: Inspect> Session<T> {
+    fn load(solution: T, source: Source) -> Result<Self> {
+        let (input, parsed, parse_time) = Self::read(solution.day(), source)?;
+        Ok(Self {
+            solution,
+            source,
+            input,
+            parsed,
+            parse_time,
+        })
+    }
+
+    fn read(day: u8, source: Source) -> Result<(String, T::Parsed, Duration)> {
+        let path = match source {
+            Source::Input => input_path(day),
+            Source::Sample(part) => sample_path(day, part),
+        };
+        let input = read_input(&path)?;
+        let start = Instant::now();
+        let parsed = T::parse(&input).map_err(|err| report_parse_error(err, &path))?;
+        Ok((input, parsed, start.elapsed()))
+    }
+
+    fn switch(&mut self, source: Source) -> Result<Action> {
+        let (input, parsed, parse_time) = Self::read(self.solution.day(), source)?;
+        self.source = source;
+        self.input = input;
+        self.parsed = parsed;
+        self.parse_time = parse_time;
+        Ok(Action::Print(format!(
+            "Loaded {} ({} bytes, parsing took: {:?})",
+            source,
+            self.input.len(),
+            self.parse_time
+        )))
+    }
+
+    fn execute(&mut self, line: &str) -> Result<Action> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let output = match words.as_slice() {
+            [] => String::new(),
+            ["help"] => HELP.to_string(),
+            ["quit" | "exit"] => return Ok(Action::Quit),
+            ["1" | "part1"] => {
+                let start = Instant::now();
+                let answer = T::part1_parsed(&self.parsed)?;
+                format!("Part 1: {}, took: {:?}", answer, start.elapsed())
+            }
+            ["2" | "part2"] => {
+                let start = Instant::now();
+                let answer = T::part2_parsed(&self.parsed)?;
+                format!("Part 2: {}, took: {:?}", answer, start.elapsed())
+            }
+            ["dump"] => format!("Structures: {}", T::structures().join(", ")),
+            ["dump", structure] => match T::dump(&self.parsed, structure) {
+                Some(dump) => dump,
+                None => bail!(
+                    "Unknown structure {}, expected one of: {}",
+                    structure,
+                    T::structures().join(", ")
+                ),
+            },
+            ["set"] if T::parameters().is_empty() => "No parameters".to_string(),
+            ["set"] => {
+                let overrides = params::all();
+                T::parameters()
+                    .iter()
+                    .map(|parameter| {
+                        let value = overrides
+                            .iter()
+                            .find(|(n, _)| n == parameter.name)
+                            .map_or("default", |(_, v)| v.as_str());
+                        format!("{} = {} ({})", parameter.name, value, parameter.description)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            ["set", name, value] => {
+                if let Err(err) = (parameter::<T>(name)?.check)(value) {
+                    bail!("Invalid value {} for parameter {}: {}", value, name, err);
+                }
+                params::set(name, value);
+                format!("{} = {}", name, value)
+            }
+            ["unset", name] => {
+                parameter::<T>(name)?;
+                params::unset(name);
+                format!("{} reset to default", name)
+            }
+            ["input"] => return self.switch(Source::Input),
+            ["sample"] => return self.switch(Source::Sample(1)),
+            ["sample", part] => return self.switch(Source::Sample(part.parse()?)),
+            ["reload"] => return self.switch(self.source),
+            _ => bail!("Unknown command: {} (try 'help')", line),
+        };
+        Ok(Action::Print(output))
+    }
+}
+
+fn parameter<T: Inspect>(name: &str) -> Result<&'static Parameter> {
+    T::parameters()
+        .iter()
+        .find(|parameter| parameter.name == name)
+        .ok_or_else(|| anyhow::anyhow!("Unknown parameter {}", name))
+}
+
+/// Runs an interactive session for a day on stdin/stdout
+pub fn start<T: Inspect>(solution: T, sample: bool) -> Result<()> {
+    let day = solution.day();
+    let source = if sample {
+        Source::Sample(1)
+    } else {
+        Source::Input
+    };
+    params::clear();
+    let mut session = Session::load(solution, source)?;
+    println!(
+        "Day {:02} REPL, {} loaded (parsing took: {:?}). Type 'help' for commands.",
+        day, source, session.parse_time
+    );
+
+    let mut stdin = io::stdin().lock();
+    loop {
+        print!("day{:02}> ", day);
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if stdin.read_line(&mut line)? == 0 {
+            break;
+        }
+        match session.execute(line.trim()) {
+            Ok(Action::Print(output)) if output.is_empty() => {}
+            Ok(Action::Print(output)) => println!("{}", output),
+            Ok(Action::Quit) => break,
+            Err(err) => println!("error: {}", err),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{day05::Day05, day08::Day08};
+
+    fn print(action: Result<Action>) -> String {
+        match action.unwrap() {
+            Action::Print(output) => output,
+            Action::Quit => panic!("Unexpected quit"),
+        }
+    }
+
+    #[test]
+    fn test_dump_and_run() {
+        let mut session = Session::load(Day05, Source::Sample(1)).unwrap();
+        assert_eq!(print(session.execute("dump merged")), "3-5\n10-20");
+        assert!(print(session.execute("part2")).starts_with("Part 2: 14,"));
+        assert!(session.execute("dump nothing").is_err());
+        assert!(matches!(session.execute("quit"), Ok(Action::Quit)));
+    }
+
+    #[test]
+    fn test_set_parameter() {
+        let mut session = Session::load(Day08, Source::Sample(1)).unwrap();
+        assert!(session.execute("set unknown 1").is_err());
+        assert!(session.execute("unset unknown").is_err());
+        assert!(session.execute("set connections abc").is_err());
+        assert!(params::all().is_empty());
+        print(session.execute("set connections 1"));
+        assert_eq!(print(session.execute("dump boxes")).lines().count(), 19);
+        assert!(print(session.execute("1")).starts_with("Part 1: 2,"));
+        print(session.execute("unset connections"));
+        assert!(print(session.execute("1")).starts_with("Part 1: 40,"));
+    }
+}
@@ -0,0 +1,511 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Result;
+
+use crate::{
+    days::{DAYS, with_day},
+    sandbox::{self, Crash, Limits, PartResult, solve_part},
+    solution::{Solution, answers_path, input_path, read_input, sample_path},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Verification {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    /// No known answer to compare against
+    Unknown,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Outcome {
+    Answer(String),
+    Failed(String),
+    /// The sandboxed process died before finishing the part
+    Crashed(Crash),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct PartReport {
+    part: u8,
+    outcome: Outcome,
+    verification: Verification,
+    elapsed: Duration,
+    /// Peak heap usage in bytes
+    memory: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct DayReport {
+    day: u8,
+    /// Why the day could not be run at all, e.g. a missing input
+    error: Option<String>,
+    parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    fn total_time(&self) -> Duration {
+        self.parts.iter().map(|part| part.elapsed).sum()
+    }
+}
+
+impl PartReport {
+    fn new(result: PartResult, expected: &[String]) -> Self {
+        let verification = match (&result.outcome, expected.get(result.part as usize - 1)) {
+            (Ok(answer), Some(expected)) if answer == expected => Verification::Correct,
+            (Ok(_), Some(expected)) if !expected.is_empty() => Verification::Wrong {
+                expected: expected.clone(),
+            },
+            _ => Verification::Unknown,
+        };
+        Self {
+            part: result.part,
+            outcome: match result.outcome {
+                Ok(answer) => Outcome::Answer(answer),
+                Err(err) => Outcome::Failed(err),
+            },
+            verification,
+            elapsed: result.elapsed,
+            memory: result.memory,
+        }
+    }
+
+    fn crashed(part: u8, crash: Crash) -> Self {
+        Self {
+            part,
+            outcome: Outcome::Crashed(crash),
+            verification: Verification::Unknown,
+            elapsed: Duration::ZERO,
+            memory: 0,
+        }
+    }
+
+    /// The answer, or what went wrong instead
+    fn answer(&self) -> String {
+        match &self.outcome {
+            Outcome::Answer(answer) => answer.clone(),
+            Outcome::Failed(err) => err.clone(),
+            Outcome::Crashed(crash) => crash.to_string(),
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        match (&self.outcome, &self.verification) {
+            (Outcome::Failed(_), _) => "failed",
+            (Outcome::Crashed(_), _) => "crashed",
+            (Outcome::Answer(_), Verification::Correct) => "correct",
+            (Outcome::Answer(_), Verification::Wrong { .. }) => "wrong",
+            (Outcome::Answer(_), Verification::Unknown) => "unverified",
+        }
+    }
+}
+
+/// Runs every day and writes `report.html` and `report.md` into `output`.
+///
+/// Answers are verified against `answers.txt` next to each day's input (part 1
+/// on the first line, part 2 on the second) when it exists. With `sandbox` set,
+/// each day runs in its own child process under `limits`, so a crashing day is
+/// reported instead of aborting the whole report.
+pub fn generate(output: &Path, sample: bool, sandbox: Option<Limits>) -> Result<()> {
+    let reports = DAYS
+        .map(|day| -> Result<DayReport> {
+            log::info!("Running day {}", day);
+            let inputs = input_paths(day, sample);
+            let expected = if sample {
+                Vec::new()
+            } else {
+                read_answers(&answers_path(day))
+            };
+            match sandbox {
+                Some(limits) => Ok(run_sandboxed(day, &inputs, &expected, limits)),
+                None => with_day!(day, solution => Ok(run_day(solution, &inputs, &expected))),
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    fs::create_dir_all(output)?;
+    let html_path = output.join("report.html");
+    let markdown_path = output.join("report.md");
+    fs::write(&html_path, html(&reports))?;
+    fs::write(&markdown_path, markdown(&reports))?;
+    println!(
+        "Wrote {} and {}",
+        html_path.display(),
+        markdown_path.display()
+    );
+    Ok(())
+}
+
+/// The input of each part: the puzzle input for both, or each part's sample
+fn input_paths(day: u8, sample: bool) -> [PathBuf; 2] {
+    [1, 2].map(|part| {
+        PathBuf::from(if sample {
+            sample_path(day, part)
+        } else {
+            input_path(day)
+        })
+    })
+}
+
+fn read_answers(path: &str) -> Vec<String> {
+    fs::read_to_string(path)
+        .map(|answers| {
+            answers
+                .lines()
+                .map(|line| line.trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn run_day<T: Solution>(solution: T, paths: &[PathBuf; 2], expected: &[String]) -> DayReport {
+    let day = solution.day();
+    let mut inputs = Vec::new();
+    for path in paths {
+        match read_input(&path.to_string_lossy()) {
+            Ok(input) => inputs.push(input),
+            Err(err) => {
+                return DayReport {
+                    day,
+                    error: Some(format!("Could not read {}: {}", path.display(), err)),
+                    parts: Vec::new(),
+                };
+            }
+        }
+    }
+    let parts = (1..=2)
+        .zip(&inputs)
+        .map(|(part, input)| PartReport::new(solve_part::<T>(part, input), expected))
+        .collect();
+    DayReport {
+        day,
+        error: None,
+        parts,
+    }
+}
+
+fn run_sandboxed(day: u8, paths: &[PathBuf; 2], expected: &[String], limits: Limits) -> DayReport {
+    let skipped = |error: String| DayReport {
+        day,
+        error: Some(error),
+        parts: Vec::new(),
+    };
+    if let Some(missing) = paths.iter().find(|path| !path.exists()) {
+        return skipped(format!("Could not read {}", missing.display()));
+    }
+    let result = match sandbox::run(day, paths, limits) {
+        Ok(result) => result,
+        Err(err) => return skipped(format!("Could not start sandbox: {}", err)),
+    };
+    let mut parts = result
+        .parts
+        .into_iter()
+        .map(|part| PartReport::new(part, expected))
+        .collect::<Vec<_>>();
+    if let Some(crash) = result.crash {
+        log::warn!("Day {} {}", day, crash);
+        let finished = parts.len() as u8;
+        parts.extend((finished + 1..=2).map(|part| PartReport::crashed(part, crash.clone())));
+    }
+    DayReport {
+        day,
+        error: None,
+        parts,
+    }
+}
+
+fn format_memory(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Escapes a table cell, putting multi-line text such as parse errors on
+/// separate lines within the cell
+fn escape_markdown(text: &str) -> String {
+    text.lines()
+        .map(|line| line.replace('|', "\\|"))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+fn markdown(reports: &[DayReport]) -> String {
+    let mut markdown = String::from("# Advent of Code 2025\n\n");
+    markdown.push_str("| Day | Part | Answer | Status | Time | Memory |\n");
+    markdown.push_str("|----:|-----:|--------|--------|-----:|-------:|\n");
+    for report in reports {
+        if let Some(err) = &report.error {
+            let _ = writeln!(
+                markdown,
+                "| {} | - | {} | skipped | - | - |",
+                report.day,
+                escape_markdown(err)
+            );
+        }
+        for part in &report.parts {
+            let status = match &part.verification {
+                Verification::Wrong { expected } => format!("wrong (expected {})", expected),
+                _ => part.status().to_string(),
+            };
+            let _ = writeln!(
+                markdown,
+                "| {} | {} | {} | {} | {:?} | {} |",
+                report.day,
+                part.part,
+                escape_markdown(&part.answer()),
+                status,
+                part.elapsed,
+                format_memory(part.memory)
+            );
+        }
+    }
+    let total: Duration = reports.iter().map(DayReport::total_time).sum();
+    let _ = writeln!(markdown, "\nTotal time: {:?}", total);
+    markdown
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Horizontal bar chart of the combined runtime of both parts per day
+fn runtime_chart(reports: &[DayReport]) -> String {
+    const BAR_HEIGHT: usize = 20;
+    const LABEL_WIDTH: usize = 60;
+    const CHART_WIDTH: f64 = 500.0;
+    let slowest = reports
+        .iter()
+        .map(DayReport::total_time)
+        .max()
+        .unwrap_or_default()
+        .as_secs_f64();
+    let height = reports.len() * BAR_HEIGHT + 10;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        LABEL_WIDTH + CHART_WIDTH as usize + 120,
+        height
+    );
+    for (i, report) in reports.iter().enumerate() {
+        let total = report.total_time();
+        let width = if slowest > 0.0 {
+            total.as_secs_f64() / slowest * CHART_WIDTH
+        } else {
+            0.0
+        };
+        let y = i * BAR_HEIGHT + 5;
+        let _ = writeln!(
+            svg,
+            "  <text x=\"0\" y=\"{}\">Day {:02}</text>",
+            y + 14,
+            report.day
+        );
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"#4a90d9\"><title>{:?}</title></rect>",
+            LABEL_WIDTH,
+            y + 2,
+            width,
+            BAR_HEIGHT - 4,
+            total
+        );
+        let _ = writeln!(
+            svg,
+            "  <text x=\"{:.1}\" y=\"{}\">{:?}</text>",
+            LABEL_WIDTH as f64 + width + 5.0,
+            y + 14,
+            total
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+fn html(reports: &[DayReport]) -> String {
+    let mut rows = String::new();
+    for report in reports {
+        if let Some(err) = &report.error {
+            let _ = writeln!(
+                rows,
+                "<tr><td>{}</td><td>-</td><td>{}</td><td class=\"skipped\">skipped</td><td>-</td><td>-</td></tr>",
+                report.day,
+                escape_html(err)
+            );
+        }
+        for part in &report.parts {
+            let title = match &part.verification {
+                Verification::Wrong { expected } => {
+                    format!(" title=\"expected {}\"", escape_html(expected))
+                }
+                _ => String::new(),
+            };
+            let _ = writeln!(
+                rows,
+                "<tr><td>{}</td><td>{}</td><td><code>{}</code></td><td class=\"{}\"{}>{}</td><td>{:?}</td><td>{}</td></tr>",
+                report.day,
+                part.part,
+                escape_html(&part.answer()),
+                part.status(),
+                title,
+                part.status(),
+                part.elapsed,
+                format_memory(part.memory)
+            );
+        }
+    }
+    let total: Duration = reports.iter().map(DayReport::total_time).sum();
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code 2025</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; margin-bottom: 2em; }}
+th, td {{ border: 1px solid #ccc; padding: 4px 10px; text-align: left; }}
+.correct {{ color: #2a7d2a; }}
+.wrong, .failed {{ color: #c0392b; font-weight: bold; }}
+.unverified, .skipped {{ color: #888; }}
+</style>
+</head>
+<body>
+<h1>Advent of Code 2025</h1>
+<table>
+<tr><th>Day</th><th>Part</th><th>Answer</th><th>Status</th><th>Time</th><th>Memory</th></tr>
+{}</table>
+<h2>Runtime per day</h2>
+{}
+<p>Total time: {:?}</p>
+</body>
+</html>
+"#,
+        rows,
+        runtime_chart(reports),
+        total
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{day05::Day05, day11::Day11};
+
+    fn part(part: u8, answer: &str, verification: Verification, millis: u64) -> PartReport {
+        PartReport {
+            part,
+            outcome: Outcome::Answer(answer.to_string()),
+            verification,
+            elapsed: Duration::from_millis(millis),
+            memory: 2048,
+        }
+    }
+
+    fn reports() -> Vec<DayReport> {
+        vec![
+            DayReport {
+                day: 1,
+                error: None,
+                parts: vec![
+                    part(1, "3", Verification::Correct, 10),
+                    part(
+                        2,
+                        "<6>",
+                        Verification::Wrong {
+                            expected: "7".to_string(),
+                        },
+                        30,
+                    ),
+                ],
+            },
+            DayReport {
+                day: 2,
+                error: Some("Could not read input".to_string()),
+                parts: Vec::new(),
+            },
+            DayReport {
+                day: 3,
+                error: None,
+                parts: vec![
+                    part(1, "357", Verification::Unknown, 0),
+                    PartReport::crashed(2, Crash::OutOfMemory),
+                ],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_run_day_verifies_answers() {
+        let paths = input_paths(5, true);
+        let report = run_day(Day05, &paths, &["3".to_string(), "15".to_string()]);
+        assert_eq!(report.parts[0].verification, Verification::Correct);
+        assert_eq!(
+            report.parts[1].verification,
+            Verification::Wrong {
+                expected: "15".to_string()
+            }
+        );
+
+        let missing = run_day(Day05, &[paths[0].clone(), "does/not/exist.txt".into()], &[]);
+        assert!(missing.error.is_some());
+    }
+
+    #[test]
+    fn test_samples_are_per_part() {
+        let paths = input_paths(11, true);
+        assert!(paths[1].ends_with("sample_part2.txt"));
+        let report = run_day(Day11, &paths, &[]);
+        assert_eq!(report.parts[0].outcome, Outcome::Answer("5".to_string()));
+        assert_eq!(report.parts[1].outcome, Outcome::Answer("2".to_string()));
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = markdown(&reports());
+        assert!(markdown.contains("| 1 | 1 | 3 | correct | 10ms | 2.0 KiB |"));
+        assert!(markdown.contains("| 1 | 2 | <6> | wrong (expected 7) | 30ms | 2.0 KiB |"));
+        assert!(markdown.contains("| 2 | - | Could not read input | skipped | - | - |"));
+        assert!(markdown.contains("| 3 | 2 | out of memory | crashed | 0ns | 0 B |"));
+        assert!(markdown.ends_with("Total time: 40ms\n"));
+    }
+
+    #[test]
+    fn test_markdown_cells_stay_on_one_row() {
+        let mut reports = reports();
+        reports[0].parts[0].outcome = Outcome::Failed("Bad input | here\nat line 2".to_string());
+        let markdown = markdown(&reports);
+        assert!(markdown.contains("| 1 | 1 | Bad input \\| here<br>at line 2 | failed |"));
+    }
+
+    #[test]
+    fn test_html_is_escaped_and_has_chart() {
+        let html = html(&reports());
+        assert!(html.contains("<code>&lt;6&gt;</code>"));
+        assert!(html.contains("<svg"));
+        assert_eq!(html.matches("<rect").count(), 3);
+        assert!(html.contains("width=\"500.0\""));
+    }
+
+    #[test]
+    fn test_format_memory() {
+        assert_eq!(format_memory(512), "512 B");
+        assert_eq!(format_memory(3 * 1024 * 1024 / 2), "1.5 MiB");
+    }
+}
@@ -0,0 +1,372 @@
+use std::{
+    fmt::{Display, Formatter},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow};
+
+use crate::{alloc::measure_peak, solution::Solution};
+
+/// Resource limits applied to a sandboxed day
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// CPU time in seconds, summed over all threads
+    pub cpu_seconds: u64,
+    /// Address space in MiB
+    pub memory_mb: u64,
+    /// Wall-clock time in seconds, for days that block without using the CPU
+    pub wall_seconds: u64,
+}
+
+/// Why a sandboxed day stopped before producing an answer
+#[derive(Clone, Debug, PartialEq)]
+pub enum Crash {
+    Panicked(String),
+    OutOfMemory,
+    CpuLimit,
+    TimedOut,
+    Signal(i32),
+    Exited(i32),
+}
+
+impl Display for Crash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Crash::Panicked(message) => write!(f, "panicked: {}", message),
+            Crash::OutOfMemory => write!(f, "out of memory"),
+            Crash::CpuLimit => write!(f, "CPU time limit exceeded"),
+            Crash::TimedOut => write!(f, "wall-clock time limit exceeded"),
+            Crash::Signal(signal) => write!(f, "killed by signal {}", signal),
+            Crash::Exited(code) => write!(f, "exited with code {}", code),
+        }
+    }
+}
+
+/// What the child reported for a single part
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    pub outcome: Result<String, String>,
+    pub elapsed: Duration,
+    pub memory: usize,
+}
+
+/// Result of running a day in a child process: the parts it finished, and
+/// the crash that stopped it, if any.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SandboxResult {
+    pub parts: Vec<PartResult>,
+    pub crash: Option<Crash>,
+}
+
+/// Runs both parts of `day`, each on its own input, in a child process of the
+/// current binary, so that panics, aborts and runaway resource usage only take
+/// down that child.
+pub fn run(day: u8, inputs: &[PathBuf; 2], limits: Limits) -> Result<SandboxResult> {
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .arg("sandbox-child")
+        .arg("--day")
+        .arg(day.to_string())
+        .arg("--input")
+        .arg(&inputs[0])
+        .arg("--part2-input")
+        .arg(&inputs[1])
+        .env("RUST_BACKTRACE", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    apply_limits(&mut command, limits);
+
+    let cpu_before = children_cpu_time();
+    let mut child = command.spawn()?;
+    // Drain the pipes while waiting, so a chatty child can't block on a full one
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let stdout = thread::spawn(move || read_lossy(&mut stdout));
+    let stderr = thread::spawn(move || read_lossy(&mut stderr));
+
+    let deadline = Instant::now() + Duration::from_secs(limits.wall_seconds);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let cpu_used = children_cpu_time().saturating_sub(cpu_before);
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    let parts = stdout.lines().filter_map(parse_line).collect();
+    let crash = match status {
+        Some(status) => {
+            let cpu_exhausted = cpu_used >= Duration::from_secs(limits.cpu_seconds);
+            classify(status, &stderr, cpu_exhausted)
+        }
+        None => Some(Crash::TimedOut),
+    };
+    Ok(SandboxResult { parts, crash })
+}
+
+fn read_lossy(pipe: &mut impl Read) -> String {
+    let mut bytes = Vec::new();
+    let _ = pipe.read_to_end(&mut bytes);
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// CPU time used by all the children of this process that have been waited for
+#[cfg(unix)]
+fn children_cpu_time() -> Duration {
+    // SAFETY: getrusage only writes to the struct we pass it
+    let usage = unsafe {
+        let mut usage = std::mem::zeroed::<libc::rusage>();
+        libc::getrusage(libc::RUSAGE_CHILDREN, &mut usage);
+        usage
+    };
+    let time = |t: libc::timeval| {
+        Duration::from_secs(t.tv_sec as u64) + Duration::from_micros(t.tv_usec as u64)
+    };
+    time(usage.ru_utime) + time(usage.ru_stime)
+}
+
+#[cfg(not(unix))]
+fn children_cpu_time() -> Duration {
+    Duration::ZERO
+}
+
+#[cfg(unix)]
+fn apply_limits(command: &mut Command, limits: Limits) {
+    use std::os::unix::process::CommandExt;
+
+    let memory = limits.memory_mb.saturating_mul(1024 * 1024) as libc::rlim_t;
+    // SIGXCPU at the soft limit, SIGKILL a second later if it is ignored
+    let cpu = libc::rlimit {
+        rlim_cur: limits.cpu_seconds as libc::rlim_t,
+        rlim_max: limits.cpu_seconds.saturating_add(1) as libc::rlim_t,
+    };
+    let memory = libc::rlimit {
+        rlim_cur: memory,
+        rlim_max: memory,
+    };
+    // SAFETY: the closure only calls setrlimit, which is async-signal-safe, between
+    // fork and exec, and setrlimit only reads the structs we pass it
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_CPU, &cpu) != 0
+                || libc::setrlimit(libc::RLIMIT_AS, &memory) != 0
+            {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn apply_limits(_command: &mut Command, _limits: Limits) {
+    log::warn!("Resource limits are only supported on unix");
+}
+
+/// Works out why the child stopped, `None` if it finished normally.
+/// `cpu_exhausted` tells whether it used up its CPU time, since the kernel
+/// also sends SIGKILL for other reasons, like running the system out of memory.
+fn classify(status: ExitStatus, stderr: &str, cpu_exhausted: bool) -> Option<Crash> {
+    if status.success() {
+        return None;
+    }
+    // The allocation error handler prints this before aborting
+    if stderr.contains("memory allocation of") {
+        return Some(Crash::OutOfMemory);
+    }
+    if let Some((_, panic)) = stderr.split_once("panicked at ") {
+        let message = panic
+            .lines()
+            .skip(1)
+            .take_while(|line| !line.starts_with("note:") && !line.starts_with("stack backtrace:"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        return Some(Crash::Panicked(message));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        match status.signal() {
+            Some(libc::SIGXCPU) => return Some(Crash::CpuLimit),
+            Some(libc::SIGKILL) if cpu_exhausted => return Some(Crash::CpuLimit),
+            Some(signal) => return Some(Crash::Signal(signal)),
+            None => {}
+        }
+    }
+    Some(Crash::Exited(status.code().unwrap_or(-1)))
+}
+
+// The child reports one tab separated line per part:
+// `<part>\t<elapsed ns>\t<memory bytes>\t<ok|err>\t<answer or error>`
+
+fn format_line(result: &PartResult) -> String {
+    let (kind, text) = match &result.outcome {
+        Ok(answer) => ("ok", answer),
+        Err(err) => ("err", err),
+    };
+    format!(
+        "{}\t{}\t{}\t{}\t{}",
+        result.part,
+        result.elapsed.as_nanos(),
+        result.memory,
+        kind,
+        text.replace('\\', "\\\\").replace('\n', "\\n")
+    )
+}
+
+fn parse_line(line: &str) -> Option<PartResult> {
+    let mut fields = line.splitn(5, '\t');
+    let part = fields.next()?.parse().ok()?;
+    let elapsed = Duration::from_nanos(fields.next()?.parse().ok()?);
+    let memory = fields.next()?.parse().ok()?;
+    let kind = fields.next()?;
+    let text = unescape(fields.next()?);
+    let outcome = match kind {
+        "ok" => Ok(text),
+        "err" => Err(text),
+        _ => return None,
+    };
+    Some(PartResult {
+        part,
+        outcome,
+        elapsed,
+        memory,
+    })
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            (c, _) => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// Entry point of the child process: solves both parts, each on its own input,
+/// and reports each on stdout as soon as it is done, so finished parts survive
+/// a later crash.
+pub fn child<T: Solution>(_solution: T, inputs: [&Path; 2]) -> Result<()> {
+    let inputs = inputs
+        .iter()
+        .map(|input| {
+            let path = input.to_string_lossy();
+            crate::solution::read_input(&path)
+                .map_err(|err| anyhow!("Could not read {}: {}", path, err))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut stdout = io::stdout().lock();
+    for (part, input) in (1..=2).zip(&inputs) {
+        writeln!(stdout, "{}", format_line(&solve_part::<T>(part, input)))?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+/// Solves a single part in this process, measuring its time and peak memory
+pub fn solve_part<T: Solution>(part: u8, input: &str) -> PartResult {
+    let start = Instant::now();
+    let (result, memory) = measure_peak(|| match part {
+        1 => T::part1(input),
+        _ => T::part2(input),
+    });
+    PartResult {
+        part,
+        outcome: result
+            .map(|answer| answer.to_string())
+            .map_err(|err| err.to_string()),
+        elapsed: start.elapsed(),
+        memory,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use std::os::unix::process::ExitStatusExt;
+
+    #[test]
+    fn test_line_round_trip() {
+        let result = PartResult {
+            part: 2,
+            outcome: Err("line one\nline\\two\\n\tand tab".to_string()),
+            elapsed: Duration::from_micros(1234),
+            memory: 4096,
+        };
+        assert_eq!(parse_line(&format_line(&result)), Some(result));
+        assert_eq!(parse_line("garbage"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_classify_panic() {
+        let stderr = "thread 'main' (1234) panicked at src/days/day09/mod.rs:156:25:\n\
+                      internal error: entered unreachable code\n\
+                      note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+        assert_eq!(
+            classify(ExitStatus::from_raw(101 << 8), stderr, false),
+            Some(Crash::Panicked(
+                "internal error: entered unreachable code".to_string()
+            ))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_classify_signals_and_exits() {
+        assert_eq!(classify(ExitStatus::from_raw(0), "", false), None);
+        assert_eq!(
+            classify(
+                ExitStatus::from_raw(libc::SIGABRT),
+                "memory allocation of 1073741824 bytes failed\n",
+                false
+            ),
+            Some(Crash::OutOfMemory)
+        );
+        assert_eq!(
+            classify(ExitStatus::from_raw(libc::SIGXCPU), "", false),
+            Some(Crash::CpuLimit)
+        );
+        assert_eq!(
+            classify(ExitStatus::from_raw(libc::SIGKILL), "", true),
+            Some(Crash::CpuLimit)
+        );
+        // Killed well within its CPU time, e.g. by the OOM killer
+        assert_eq!(
+            classify(ExitStatus::from_raw(libc::SIGKILL), "", false),
+            Some(Crash::Signal(libc::SIGKILL))
+        );
+        assert_eq!(
+            classify(ExitStatus::from_raw(libc::SIGSEGV), "", false),
+            Some(Crash::Signal(libc::SIGSEGV))
+        );
+        assert_eq!(
+            classify(ExitStatus::from_raw(3 << 8), "", false),
+            Some(Crash::Exited(3))
+        );
+    }
+}
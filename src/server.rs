@@ -0,0 +1,464 @@
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    panic::{self, AssertUnwindSafe},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+
+use crate::{
+    days::{DAYS, with_day},
+    input::normalize,
+    params,
+    repl::Inspect,
+    solution::Solution,
+    utils::parse::ParseError,
+};
+
+/// Largest request body we are willing to buffer
+const MAX_BODY: usize = 16 * 1024 * 1024;
+/// Longest request line or header line, in bytes
+const MAX_LINE: usize = 8 * 1024;
+const MAX_HEADERS: usize = 100;
+/// Connections handled at once, more are turned away with a 503
+const MAX_CONNECTIONS: usize = 16;
+/// How long a single read or write on a connection may block
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+/// Stack size of the connection threads, as large as the main thread's
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+static ACTIVE_CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, format!("{{\"error\":{}}}", json_string(message)))
+    }
+
+    fn write_to(&self, stream: &mut impl Write) -> io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            414 => "URI Too Long",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason,
+            self.body.len(),
+            self.body
+        )?;
+        stream.flush()
+    }
+}
+
+/// Serves the solutions over HTTP on localhost until the process is killed.
+///
+/// - `GET /days` lists the registered days
+/// - `POST /days/{n}/parts/{p}` solves a part with the request body as input,
+///   query parameters (e.g. `?connections=10`) override solution parameters
+///
+/// Each connection gets its own thread, up to [`MAX_CONNECTIONS`] at once. A
+/// panicking solution only fails its request, but a stack overflow still
+/// aborts the whole server, as it can't be caught.
+pub fn serve(port: u16) -> Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    log::info!("Listening on http://{}", listener.local_addr()?);
+    for stream in listener.incoming() {
+        let stream = match stream.and_then(|stream| {
+            stream.set_read_timeout(Some(IO_TIMEOUT))?;
+            stream.set_write_timeout(Some(IO_TIMEOUT))?;
+            Ok(stream)
+        }) {
+            Ok(stream) => stream,
+            Err(err) => {
+                log::warn!("Failed to accept connection: {}", err);
+                continue;
+            }
+        };
+        let Some(slot) = ConnectionSlot::acquire() else {
+            log::warn!("Too many connections, turning one away");
+            let _ = Response::error(503, "Too many connections").write_to(&mut &stream);
+            continue;
+        };
+        let spawned = thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn(move || {
+                let _slot = slot;
+                if let Err(err) = handle_connection(stream) {
+                    log::warn!("Connection failed: {}", err);
+                }
+            });
+        if let Err(err) = spawned {
+            log::warn!("Failed to start connection thread: {}", err);
+        }
+    }
+    Ok(())
+}
+
+/// One of the [`MAX_CONNECTIONS`] connections, freed again on drop
+struct ConnectionSlot;
+
+impl ConnectionSlot {
+    fn acquire() -> Option<Self> {
+        ACTIVE_CONNECTIONS
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                (active < MAX_CONNECTIONS).then_some(active + 1)
+            })
+            .ok()
+            .map(|_| ConnectionSlot)
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        ACTIVE_CONNECTIONS.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn handle_connection(mut stream: TcpStream) -> io::Result<()> {
+    let response = match read_request(&mut BufReader::new(&stream)) {
+        Ok(request) => {
+            log::info!("{} {}", request.method, request.path);
+            route(&request)
+        }
+        Err(response) => response,
+    };
+    response.write_to(&mut stream)
+}
+
+/// Reads a line of at most [`MAX_LINE`] bytes, `None` if it is longer
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader
+        .by_ref()
+        .take(MAX_LINE as u64 + 1)
+        .read_line(&mut line)?;
+    Ok((line.len() <= MAX_LINE).then_some(line))
+}
+
+fn read_request(reader: &mut impl BufRead) -> Result<Request, Response> {
+    let bad_request = |message: &str| Response::error(400, message);
+    let line = read_line(reader)
+        .map_err(|_| bad_request("Unreadable request line"))?
+        .ok_or_else(|| Response::error(414, "Request line too long"))?;
+    let [method, target, version] = line.split_whitespace().collect::<Vec<_>>()[..] else {
+        return Err(bad_request("Malformed request line"));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(bad_request("Only HTTP/1.x is supported"));
+    }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (name.to_string(), value.to_string())
+        })
+        .collect();
+
+    let mut content_length = 0;
+    for headers in 0.. {
+        let header = read_line(reader)
+            .map_err(|_| bad_request("Unreadable header"))?
+            .ok_or_else(|| Response::error(431, "Header too long"))?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if headers == MAX_HEADERS {
+            return Err(Response::error(431, "Too many headers"));
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value
+                .trim()
+                .parse()
+                .map_err(|_| bad_request("Invalid Content-Length"))?;
+        }
+    }
+    if content_length > MAX_BODY {
+        return Err(Response::error(413, "Request body too large"));
+    }
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| bad_request("Truncated body"))?;
+    let body = String::from_utf8(body).map_err(|_| bad_request("Body is not valid UTF-8"))?;
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query,
+        body,
+    })
+}
+
+fn route(request: &Request) -> Response {
+    let segments = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .collect::<Vec<_>>();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["days"]) => list_days(),
+        ("POST", ["days", day, "parts", part]) => {
+            let (Ok(day), Ok(part)) = (day.parse::<u8>(), part.parse::<u8>()) else {
+                return Response::error(404, "Not found");
+            };
+            if !DAYS.contains(&day) || !(1..=2).contains(&part) {
+                return Response::error(404, &format!("No part {} for day {}", part, day));
+            }
+            params::clear();
+            for (name, value) in &request.query {
+                params::set(name, value);
+            }
+            let input = normalize(&request.body);
+            let solve_day = || -> Result<Response> {
+                with_day!(day, solution => Ok(solve(solution, part, &input)))
+            };
+            solve_day().unwrap_or_else(|err| Response::error(500, &err.to_string()))
+        }
+        (_, ["days"] | ["days", _, "parts", _]) => Response::error(405, "Method not allowed"),
+        _ => Response::error(404, "Not found"),
+    }
+}
+
+fn list_days() -> Response {
+    let days = DAYS
+        .map(|day| -> Result<String> { with_day!(day, solution => Ok(describe(solution))) })
+        .collect::<Result<Vec<_>>>();
+    match days {
+        Ok(days) => Response::json(200, format!("[{}]", days.join(","))),
+        Err(err) => Response::error(500, &err.to_string()),
+    }
+}
+
+fn describe<T: Inspect>(solution: T) -> String {
+    let structures = T::structures()
+        .iter()
+        .map(|structure| json_string(structure))
+        .collect::<Vec<_>>();
+    let parameters = T::parameters()
+        .iter()
+        .map(|parameter| {
+            format!(
+                "{{\"name\":{},\"description\":{}}}",
+                json_string(parameter.name),
+                json_string(parameter.description)
+            )
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "{{\"day\":{},\"parts\":[1,2],\"structures\":[{}],\"parameters\":[{}]}}",
+        solution.day(),
+        structures.join(","),
+        parameters.join(",")
+    )
+}
+
+fn solve<T: Solution>(solution: T, part: u8, input: &str) -> Response {
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| match part {
+        1 => T::part1(input),
+        _ => T::part2(input),
+    }));
+    let elapsed = start.elapsed();
+    let prefix = format!(
+        "\"day\":{},\"part\":{},\"elapsed_us\":{}",
+        solution.day(),
+        part,
+        elapsed.as_micros()
+    );
+    match result {
+        Ok(Ok(answer)) => Response::json(
+            200,
+            format!(
+                "{{{},\"answer\":{}}}",
+                prefix,
+                json_string(&answer.to_string())
+            ),
+        ),
+        Ok(Err(err)) => match err.downcast_ref::<ParseError>() {
+            Some(parse_error) => Response::json(
+                422,
+                format!(
+                    "{{{},\"parse_error\":{{\"message\":{},\"line\":{},\"column\":{},\"excerpt\":{}}}}}",
+                    prefix,
+                    json_string(parse_error.message()),
+                    parse_error.line,
+                    parse_error.column,
+                    json_string(&parse_error.excerpt)
+                ),
+            ),
+            None => Response::json(
+                500,
+                format!("{{{},\"error\":{}}}", prefix, json_string(&err.to_string())),
+            ),
+        },
+        Err(_) => Response::json(
+            500,
+            format!("{{{},\"error\":\"solution panicked\"}}", prefix),
+        ),
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(path: &str, body: &str) -> Response {
+        route(&Request {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            query: Vec::new(),
+            body: body.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_read_request() {
+        let raw = "POST /days/5/parts/2?connections=10 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 5\r\n\r\n3-5\n\n1";
+        let request = read_request(&mut raw.as_bytes()).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/days/5/parts/2");
+        assert_eq!(
+            request.query,
+            vec![("connections".to_string(), "10".to_string())]
+        );
+        assert_eq!(request.body, "3-5\n\n");
+    }
+
+    #[test]
+    fn test_request_limits() {
+        let long_target = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE));
+        assert_eq!(
+            read_request(&mut long_target.as_bytes())
+                .unwrap_err()
+                .status,
+            414
+        );
+        let long_header = format!("GET /days HTTP/1.1\r\nX: {}\r\n\r\n", "a".repeat(MAX_LINE));
+        assert_eq!(
+            read_request(&mut long_header.as_bytes())
+                .unwrap_err()
+                .status,
+            431
+        );
+        let many_headers = format!(
+            "GET /days HTTP/1.1\r\n{}\r\n",
+            "X: 1\r\n".repeat(MAX_HEADERS + 1)
+        );
+        assert_eq!(
+            read_request(&mut many_headers.as_bytes())
+                .unwrap_err()
+                .status,
+            431
+        );
+        let enough_headers = format!(
+            "GET /days HTTP/1.1\r\n{}\r\n",
+            "X: 1\r\n".repeat(MAX_HEADERS)
+        );
+        assert!(read_request(&mut enough_headers.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_connection_slots() {
+        let slots = (0..MAX_CONNECTIONS)
+            .map_while(|_| ConnectionSlot::acquire())
+            .collect::<Vec<_>>();
+        assert_eq!(slots.len(), MAX_CONNECTIONS);
+        assert!(ConnectionSlot::acquire().is_none());
+        drop(slots);
+        assert!(ConnectionSlot::acquire().is_some());
+    }
+
+    #[test]
+    fn test_solve_part() {
+        let response = post(
+            "/days/5/parts/2",
+            "3-5\r\n10-14\r\n16-20\r\n12-18\r\n\r\n1\r\n",
+        );
+        assert_eq!(response.status, 200);
+        assert!(response.body.ends_with("\"answer\":\"14\"}"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let response = post("/days/2/parts/1", "11-22,95-1x5");
+        assert_eq!(response.status, 422);
+        assert!(response.body.contains("\"line\":1,\"column\":10"));
+    }
+
+    #[test]
+    fn test_unknown_routes() {
+        assert_eq!(post("/days/13/parts/1", "").status, 404);
+        assert_eq!(post("/days/1/parts/3", "").status, 404);
+        assert_eq!(post("/days", "").status, 405);
+        assert_eq!(post("/nothing", "").status, 404);
+    }
+
+    #[test]
+    fn test_list_days() {
+        let response = list_days();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body.matches("\"day\":").count(), 12);
+        assert!(response.body.contains("{\"name\":\"connections\""));
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+}
@@ -1,10 +1,16 @@
 use std::{
     fmt::{Debug, Display},
     fs,
+    path::Path,
     time::Instant,
 };
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+
+use crate::{
+    input::normalize,
+    utils::{parse::ParseError, viz::Scene},
+};
 
 pub trait Solution {
     type Answer: Debug + Display + Clone + PartialEq;
@@ -12,6 +18,12 @@ pub trait Solution {
     fn part1(input: &str) -> Result<Self::Answer>;
     fn part2(input: &str) -> Result<Self::Answer>;
 
+    /// Frames showing how the day works through its input, for `--visualize`.
+    /// Days without a visualization return none.
+    fn visualize(_input: &str) -> Result<Vec<Scene>> {
+        Ok(Vec::new())
+    }
+
     fn solve(input: &str) -> Result<()> {
         let start = Instant::now();
         let part1 = Self::part1(input)?;
@@ -27,9 +39,9 @@ pub trait Solution {
 
     fn run(&self) -> Result<()> {
         let day = self.day();
-        let path = format!("./src/days/day{day:02}/input.txt");
+        let path = input_path(day);
         let start = Instant::now();
-        let input = fs::read_to_string(path)?;
+        let input = read_input(&path)?;
         println!("Day {:02}", day);
         println!("====================");
         println!(
@@ -37,29 +49,55 @@ pub trait Solution {
             start.elapsed(),
             input.len()
         );
-        Self::solve(&input)
+        Self::solve(&input).map_err(|err| report_parse_error(err, &path))
     }
 
     #[cfg(test)]
     fn run_test1(&self) -> Self::Answer {
-        let day = self.day();
-        let path = format!("./src/days/day{day:02}/sample.txt");
-        let input = fs::read_to_string(path).unwrap();
+        let input = read_input(&sample_path(self.day(), 1)).unwrap();
         Self::part1(&input).expect("Part 1 failed")
     }
 
     #[cfg(test)]
     fn run_test2(&self) -> Self::Answer {
-        use std::path::Path;
-
-        let day = self.day();
-        let part2_path = format!("./src/days/day{day:02}/sample_part2.txt");
-        let path = if Path::new(&part2_path).exists() {
-            part2_path
-        } else {
-            format!("./src/days/day{day:02}/sample.txt")
-        };
-        let input = fs::read_to_string(path).unwrap();
+        let input = read_input(&sample_path(self.day(), 2)).unwrap();
         Self::part2(&input).expect("Part 2 failed")
     }
 }
+
+pub fn input_path(day: u8) -> String {
+    format!("./src/days/day{day:02}/input.txt")
+}
+
+/// Known answers for the puzzle input, one part per line
+pub fn answers_path(day: u8) -> String {
+    format!("./src/days/day{day:02}/answers.txt")
+}
+
+/// Path of the sample for the given part, preferring a part specific
+/// `sample_partN.txt` over the shared `sample.txt`.
+pub fn sample_path(day: u8, part: u8) -> String {
+    let part_path = format!("./src/days/day{day:02}/sample_part{part}.txt");
+    if Path::new(&part_path).exists() {
+        part_path
+    } else {
+        format!("./src/days/day{day:02}/sample.txt")
+    }
+}
+
+/// Reads and normalizes an input file
+pub fn read_input(path: &str) -> Result<String> {
+    Ok(normalize(&fs::read_to_string(path)?))
+}
+
+/// Prints parse errors compiler-style against the file they came from,
+/// passing any other error through untouched.
+pub fn report_parse_error(err: anyhow::Error, path: &str) -> anyhow::Error {
+    match err.downcast_ref::<ParseError>() {
+        Some(parse_error) => {
+            eprintln!("{}", parse_error.render(path));
+            anyhow!("Failed to parse {}", path)
+        }
+        None => err,
+    }
+}
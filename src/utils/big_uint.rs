@@ -0,0 +1,375 @@
+#![allow(dead_code)]
+
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{Display, Formatter},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign},
+    str::FromStr,
+};
+
+use super::parse::ParseError;
+
+/// An arbitrary-precision unsigned integer, for answers that can outgrow `u64`.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub struct BigUint {
+    /// Base 2^32 digits, least significant first, without trailing zeros so
+    /// that zero is empty and every value has a single representation
+    limbs: Vec<u32>,
+}
+
+/// The largest power of ten that fits a limb, for converting to and from decimal
+const DECIMAL_BASE: u32 = 1_000_000_000;
+const DECIMAL_DIGITS: usize = 9;
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// The value as a `u64`, `None` if it doesn't fit
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(low as u64),
+            [low, high] => Some((high as u64) << 32 | low as u64),
+            _ => None,
+        }
+    }
+
+    /// Number of bits needed to write the value, 0 for zero
+    pub fn bits(&self) -> u64 {
+        self.limbs.last().map_or(0, |&top| {
+            self.limbs.len() as u64 * 32 - top.leading_zeros() as u64
+        })
+    }
+
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    /// Multiplies by `factor` and adds `addend` in place
+    fn mul_add_small(&mut self, factor: u32, addend: u32) {
+        let mut carry = addend as u64;
+        for limb in self.limbs.iter_mut() {
+            let value = *limb as u64 * factor as u64 + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+        self.normalize();
+    }
+
+    /// Divides by `divisor` in place, returning the remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+        for limb in self.limbs.iter_mut().rev() {
+            let value = remainder << 32 | *limb as u64;
+            *limb = (value / divisor as u64) as u32;
+            remainder = value % divisor as u64;
+        }
+        self.normalize();
+        remainder as u32
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        let mut big = Self {
+            limbs: vec![value as u32, (value >> 32) as u32],
+        };
+        big.normalize();
+        big
+    }
+}
+
+impl PartialEq<u64> for BigUint {
+    fn eq(&self, other: &u64) -> bool {
+        self.to_u64() == Some(*other)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+        let mut carry = 0u64;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let value = *limb as u64 + other.limbs.get(i).copied().unwrap_or(0) as u64 + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+            if carry == 0 && i >= other.limbs.len() {
+                break;
+            }
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+}
+
+impl Add<&BigUint> for BigUint {
+    type Output = BigUint;
+
+    fn add(mut self, other: &BigUint) -> BigUint {
+        self += other;
+        self
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: BigUint) -> BigUint {
+        self + &other
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    /// Schoolbook multiplication, plenty for answers of a few hundred digits
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let value = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = value as u32;
+                carry = value >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        let mut product = BigUint { limbs };
+        product.normalize();
+        product
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: BigUint) -> BigUint {
+        &self * &other
+    }
+}
+
+impl MulAssign<&BigUint> for BigUint {
+    fn mul_assign(&mut self, other: &BigUint) {
+        *self = &*self * other;
+    }
+}
+
+impl Sum for BigUint {
+    fn sum<I: Iterator<Item = BigUint>>(iter: I) -> Self {
+        iter.fold(BigUint::zero(), |sum, value| sum + &value)
+    }
+}
+
+impl<'a> Sum<&'a BigUint> for BigUint {
+    fn sum<I: Iterator<Item = &'a BigUint>>(iter: I) -> Self {
+        iter.fold(BigUint::zero(), |sum, value| sum + value)
+    }
+}
+
+impl Product for BigUint {
+    fn product<I: Iterator<Item = BigUint>>(iter: I) -> Self {
+        iter.fold(BigUint::from(1), |product, value| product * value)
+    }
+}
+
+/// Writes the value in decimal
+impl Display for BigUint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_small(DECIMAL_BASE));
+        }
+        let Some(first) = chunks.pop() else {
+            return f.pad("0");
+        };
+        let mut digits = first.to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:0width$}", chunk, width = DECIMAL_DIGITS));
+        }
+        f.pad(&digits)
+    }
+}
+
+/// Parses a non-empty string of decimal digits
+impl FromStr for BigUint {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::new("Empty number"));
+        }
+        let mut value = BigUint::zero();
+        for (i, c) in s.char_indices() {
+            let digit = c
+                .to_digit(10)
+                .ok_or_else(|| ParseError::at(s, &s[i..i + c.len_utf8()], "Invalid digit"))?;
+            value.mul_add_small(10, digit);
+        }
+        Ok(value)
+    }
+}
+
+/// A count computed with overflow checks, so that counting code can be written
+/// once and run on a plain `u64` or on a [`BigUint`] that never overflows.
+pub trait Count: Clone + Display {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn try_add(&self, other: &Self) -> Result<Self, Overflow>;
+    fn try_mul(&self, other: &Self) -> Result<Self, Overflow>;
+}
+
+impl Count for u64 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn try_add(&self, other: &Self) -> Result<Self, Overflow> {
+        self.checked_add(*other).ok_or(Overflow)
+    }
+
+    fn try_mul(&self, other: &Self) -> Result<Self, Overflow> {
+        self.checked_mul(*other).ok_or(Overflow)
+    }
+}
+
+impl Count for BigUint {
+    fn zero() -> Self {
+        BigUint::zero()
+    }
+
+    fn one() -> Self {
+        BigUint::from(1)
+    }
+
+    fn try_add(&self, other: &Self) -> Result<Self, Overflow> {
+        Ok(self.clone() + other)
+    }
+
+    fn try_mul(&self, other: &Self) -> Result<Self, Overflow> {
+        Ok(self * other)
+    }
+}
+
+/// A count that didn't fit in the type it was computed in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Overflow;
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Count overflowed a u64")
+    }
+}
+
+impl Error for Overflow {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for s in [
+            "0",
+            "7",
+            "4294967296",
+            "18446744073709551616",
+            "1000000000000000000000000000001",
+        ] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(big("007").to_string(), "7");
+        assert_eq!(format!("{:>5}", BigUint::from(42)), "   42");
+        assert!("12a".parse::<BigUint>().is_err());
+        assert!("".parse::<BigUint>().is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let max = BigUint::from(u64::MAX);
+        assert_eq!(
+            (max.clone() + BigUint::from(1)).to_string(),
+            "18446744073709551616"
+        );
+        assert_eq!(
+            (max.clone() * max.clone()).to_string(),
+            "340282366920938463426481119284349108225"
+        );
+        assert_eq!(BigUint::zero() * max.clone(), BigUint::zero());
+        let factorial = (1..=30).map(BigUint::from).product::<BigUint>();
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+        assert_eq!(
+            [1, 2, 3].map(BigUint::from).iter().sum::<BigUint>(),
+            BigUint::from(6)
+        );
+        assert_eq!(max.bits(), 64);
+    }
+
+    #[test]
+    fn test_comparison_and_conversion() {
+        let (small, max, beyond) = (
+            BigUint::from(3),
+            BigUint::from(u64::MAX),
+            big("18446744073709551616"),
+        );
+        assert!(small < max && max < beyond);
+        assert_eq!(max.cmp(&max.clone()), Ordering::Equal);
+        assert_eq!(BigUint::from(u64::MAX).to_u64(), Some(u64::MAX));
+        assert_eq!(big("18446744073709551616").to_u64(), None);
+        assert_eq!(BigUint::from(12), 12);
+    }
+
+    #[test]
+    fn test_count() {
+        assert_eq!(u64::MAX.try_add(&1), Err(Overflow));
+        assert_eq!((1u64 << 32).try_mul(&(1 << 32)), Err(Overflow));
+        assert_eq!(2u64.try_mul(&3), Ok(6));
+        let big = BigUint::from(1 << 32);
+        assert_eq!(
+            big.try_mul(&big).unwrap().to_string(),
+            "18446744073709551616"
+        );
+    }
+}
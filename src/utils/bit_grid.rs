@@ -0,0 +1,346 @@
+#![allow(dead_code)]
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+};
+
+use super::{grid::Grid, parse::ParseError, vec2d::Vec2D};
+
+/// A rectangular grid of booleans packed 64 cells to a `u64`, with `(0, 0)`
+/// in the top left corner.
+///
+/// Every row starts on a fresh word and the padding bits past the width are
+/// kept clear, so whole rows can be combined and shifted a word at a time.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+}
+
+impl BitGrid {
+    /// An empty grid with every cell cleared
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        Self {
+            words: vec![0; words_per_row * height],
+            width,
+            height,
+            words_per_row,
+        }
+    }
+
+    /// A grid with the given positions set, ignoring those outside of it
+    pub fn from_positions(
+        width: usize,
+        height: usize,
+        positions: impl IntoIterator<Item = Vec2D>,
+    ) -> Self {
+        let mut grid = Self::new(width, height);
+        for position in positions {
+            if grid.contains(&position) {
+                grid.set(&position);
+            }
+        }
+        grid
+    }
+
+    /// Parses a character map like `Grid::parse`, `cell` deciding which
+    /// characters are set
+    pub fn parse(s: &str, cell: impl FnMut(char) -> Option<bool>) -> Result<Self, ParseError> {
+        Ok(Self::from(&Grid::parse(s, cell)?))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: &Vec2D) -> bool {
+        (0..self.width as i64).contains(&position.x())
+            && (0..self.height as i64).contains(&position.y())
+    }
+
+    /// Word index and bit mask of an in-bounds position
+    fn locate(&self, position: &Vec2D) -> (usize, u64) {
+        let (x, y) = (position.x() as usize, position.y() as usize);
+        (y * self.words_per_row + x / 64, 1 << (x % 64))
+    }
+
+    /// Whether the cell is set; positions outside the grid never are
+    pub fn get(&self, position: &Vec2D) -> bool {
+        if !self.contains(position) {
+            return false;
+        }
+        let (word, mask) = self.locate(position);
+        self.words[word] & mask != 0
+    }
+
+    /// Panics if `position` is outside the grid
+    pub fn set(&mut self, position: &Vec2D) {
+        assert!(self.contains(position), "{} is outside the grid", position);
+        let (word, mask) = self.locate(position);
+        self.words[word] |= mask;
+    }
+
+    /// Panics if `position` is outside the grid
+    pub fn clear(&mut self, position: &Vec2D) {
+        assert!(self.contains(position), "{} is outside the grid", position);
+        let (word, mask) = self.locate(position);
+        self.words[word] &= !mask;
+    }
+
+    /// Number of set cells
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Positions of the set cells in row-major order
+    pub fn ones(&self) -> impl Iterator<Item = Vec2D> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let y = (i / self.words_per_row) as i64;
+            let x0 = (i % self.words_per_row * 64) as i64;
+            let mut bits = word;
+            std::iter::from_fn(move || {
+                (bits != 0).then(|| {
+                    let bit = bits.trailing_zeros() as i64;
+                    bits &= bits - 1;
+                    Vec2D::new(x0 + bit, y)
+                })
+            })
+        })
+    }
+
+    pub fn to_positions(&self) -> HashSet<Vec2D> {
+        self.ones().collect()
+    }
+
+    /// Number of set cells among the 8 around `position`
+    pub fn count_neighbours_8(&self, position: &Vec2D) -> u32 {
+        position
+            .adjacent_8()
+            .iter()
+            .filter(|neighbour| self.get(neighbour))
+            .count() as u32
+    }
+
+    /// Keeps only the cells also set in `other`, which must have the same size
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a & b);
+    }
+
+    /// Sets the cells set in `other`, which must have the same size
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a | b);
+    }
+
+    /// Clears the cells set in `other`, which must have the same size
+    pub fn subtract(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a & !b);
+    }
+
+    fn combine(&mut self, other: &BitGrid, f: impl Fn(u64, u64) -> u64) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "Grid size mismatch"
+        );
+        for (word, &other) in self.words.iter_mut().zip(&other.words) {
+            *word = f(*word, other);
+        }
+    }
+
+    /// The cells (set or not) whose number of set 8-neighbours satisfies
+    /// `predicate`, computed a word at a time
+    pub fn neighbour_count_mask(&self, predicate: impl Fn(u32) -> bool) -> BitGrid {
+        let matching = (0..=8)
+            .filter(|&count| predicate(count))
+            .collect::<Vec<_>>();
+        let mut mask = Self::new(self.width, self.height);
+        let empty = vec![0; self.words_per_row];
+        for y in 0..self.height {
+            let above = if y > 0 { self.row(y - 1) } else { &empty };
+            let below = if y + 1 < self.height {
+                self.row(y + 1)
+            } else {
+                &empty
+            };
+            let row = self.row(y);
+            for i in 0..self.words_per_row {
+                // Bit-sliced counters: bit k of planes[j] is bit j of the count for cell k
+                let mut planes = [0u64; 4];
+                for neighbours in [
+                    shifted_right(above, i),
+                    above[i],
+                    shifted_left(above, i),
+                    shifted_right(row, i),
+                    shifted_left(row, i),
+                    shifted_right(below, i),
+                    below[i],
+                    shifted_left(below, i),
+                ] {
+                    let mut carry = neighbours;
+                    for plane in planes.iter_mut() {
+                        (*plane, carry) = (*plane ^ carry, *plane & carry);
+                    }
+                }
+                let word = matching.iter().fold(0, |word, &count| {
+                    word | (0..4).fold(!0, |equal, j| {
+                        equal
+                            & if count >> j & 1 == 1 {
+                                planes[j]
+                            } else {
+                                !planes[j]
+                            }
+                    })
+                });
+                mask.words[y * self.words_per_row + i] = word & self.padding_mask(i);
+            }
+        }
+        mask
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// The bits of word `i` in a row that lie inside the grid
+    fn padding_mask(&self, i: usize) -> u64 {
+        let used = (self.width - i * 64).min(64);
+        if used == 64 { !0 } else { (1 << used) - 1 }
+    }
+}
+
+/// Word `i` of `row` with every cell replaced by its left neighbour (x - 1)
+fn shifted_right(row: &[u64], i: usize) -> u64 {
+    let carry = if i > 0 { row[i - 1] >> 63 } else { 0 };
+    row[i] << 1 | carry
+}
+
+/// Word `i` of `row` with every cell replaced by its right neighbour (x + 1)
+fn shifted_left(row: &[u64], i: usize) -> u64 {
+    let carry = row.get(i + 1).map_or(0, |next| next << 63);
+    row[i] >> 1 | carry
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        Self::from_positions(grid.width(), grid.height(), grid.find_all(&true))
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(grid: &BitGrid) -> Self {
+        let mut cells = Grid::new(grid.width, grid.height, false);
+        for position in grid.ones() {
+            cells[position] = true;
+        }
+        cells
+    }
+}
+
+/// Draws set cells as `#` and cleared ones as `.`
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let cells = Grid::from(self).map(|_, &set| if set { '#' } else { '.' });
+        write!(f, "{}", cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::rng::Rng;
+
+    fn parse(s: &str) -> BitGrid {
+        BitGrid::parse(s, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_set_clear_and_count() {
+        let mut grid = BitGrid::new(70, 3);
+        grid.set(&Vec2D::new(0, 0));
+        grid.set(&Vec2D::new(64, 1));
+        grid.set(&Vec2D::new(69, 2));
+        assert_eq!(grid.count_ones(), 3);
+        assert!(grid.get(&Vec2D::new(64, 1)));
+        assert!(!grid.get(&Vec2D::new(70, 1)));
+        grid.clear(&Vec2D::new(0, 0));
+        assert_eq!(
+            grid.ones().collect::<Vec<_>>(),
+            vec![Vec2D::new(64, 1), Vec2D::new(69, 2)]
+        );
+        assert_eq!(BitGrid::from_positions(70, 3, grid.to_positions()), grid);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = parse("##.\n.#.");
+        let b = parse(".##\n.#.");
+        let mut both = a.clone();
+        both.intersect_with(&b);
+        assert_eq!(both.to_string(), ".#.\n.#.");
+        let mut either = a.clone();
+        either.union_with(&b);
+        assert_eq!(either.to_string(), "###\n.#.");
+        let mut only_a = a;
+        only_a.subtract(&b);
+        assert_eq!(only_a.to_string(), "#..\n...");
+    }
+
+    #[test]
+    fn test_neighbour_count_mask() {
+        let grid = parse("###\n#.#\n###");
+        assert_eq!(grid.count_neighbours_8(&Vec2D::new(1, 1)), 8);
+        assert_eq!(grid.count_neighbours_8(&Vec2D::new(0, 0)), 2);
+        assert_eq!(
+            grid.neighbour_count_mask(|count| count == 8).to_string(),
+            "...\n.#.\n..."
+        );
+        assert_eq!(
+            grid.neighbour_count_mask(|count| count < 3).to_string(),
+            "#.#\n...\n#.#"
+        );
+    }
+
+    #[test]
+    fn test_neighbour_count_mask_matches_scalar_across_words() {
+        let mut rng = Rng::new(44);
+        let (width, height) = (150, 20);
+        let positions =
+            (0..1500).map(|_| Vec2D::new(rng.range(0..width as i64), rng.range(0..height as i64)));
+        let grid = BitGrid::from_positions(width, height, positions.collect::<Vec<_>>());
+        for threshold in 0..=8 {
+            let mask = grid.neighbour_count_mask(|count| count >= threshold);
+            for y in 0..height as i64 {
+                for x in 0..width as i64 {
+                    let position = Vec2D::new(x, y);
+                    assert_eq!(
+                        mask.get(&position),
+                        grid.count_neighbours_8(&position) >= threshold,
+                        "{} at threshold {}",
+                        position,
+                        threshold
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,266 @@
+#![allow(dead_code)]
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use super::{
+    parse::ParseError,
+    vec2d::{self, Vec2D},
+};
+
+/// One of the four orthogonal directions on a screen-like grid, with y pointing down.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting from `Up`
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn all() -> impl Iterator<Item = Direction> {
+        Self::ALL.into_iter()
+    }
+
+    /// Parses `^v<>`, `UDLR` and `NSEW` characters; `LR` is the horizontal subset of `UDLR`
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'U' | 'N' => Some(Direction::Up),
+            '>' | 'R' | 'E' => Some(Direction::Right),
+            'v' | 'D' | 'S' => Some(Direction::Down),
+            '<' | 'L' | 'W' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// Arrow character pointing in this direction
+    pub fn to_char(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self.index() + 2) % 4]
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn to_vec2d(self) -> Vec2D {
+        match self {
+            Direction::Up => vec2d::UP,
+            Direction::Right => vec2d::RIGHT,
+            Direction::Down => vec2d::DOWN,
+            Direction::Left => vec2d::LEFT,
+        }
+    }
+}
+
+impl From<Direction> for Vec2D {
+    fn from(direction: Direction) -> Self {
+        direction.to_vec2d()
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::from_char(c),
+            _ => None,
+        }
+        .ok_or_else(|| ParseError::at(s, s, format!("Invalid direction: {}", s)))
+    }
+}
+
+/// One of the eight compass directions, diagonals included, with y pointing down.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting from `Up`
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    pub fn all() -> impl Iterator<Item = Direction8> {
+        Self::ALL.into_iter()
+    }
+
+    /// Parses the single characters understood by `Direction`
+    pub fn from_char(c: char) -> Option<Self> {
+        Direction::from_char(c).map(Self::from)
+    }
+
+    fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+
+    /// Turns by 45° clockwise
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Turns by 45° counter-clockwise
+    pub fn turn_left(self) -> Self {
+        self.rotate(7)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    pub fn to_vec2d(self) -> Vec2D {
+        match self {
+            Direction8::Up => vec2d::UP,
+            Direction8::UpRight => vec2d::UP_RIGHT,
+            Direction8::Right => vec2d::RIGHT,
+            Direction8::DownRight => vec2d::DOWN_RIGHT,
+            Direction8::Down => vec2d::DOWN,
+            Direction8::DownLeft => vec2d::DOWN_LEFT,
+            Direction8::Left => vec2d::LEFT,
+            Direction8::UpLeft => vec2d::UP_LEFT,
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction.index() * 2]
+    }
+}
+
+impl From<Direction8> for Vec2D {
+    fn from(direction: Direction8) -> Self {
+        direction.to_vec2d()
+    }
+}
+
+/// Parses single direction characters as well as compass points like `NE` or `SW`
+impl FromStr for Direction8 {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let direction = match s {
+            "NE" => Some(Direction8::UpRight),
+            "SE" => Some(Direction8::DownRight),
+            "SW" => Some(Direction8::DownLeft),
+            "NW" => Some(Direction8::UpLeft),
+            _ => s.parse::<Direction>().ok().map(Self::from),
+        };
+        direction.ok_or_else(|| ParseError::at(s, s, format!("Invalid direction: {}", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        for (chars, direction) in [
+            ("^UN", Direction::Up),
+            (">RE", Direction::Right),
+            ("vDS", Direction::Down),
+            ("<LW", Direction::Left),
+        ] {
+            assert!(
+                chars
+                    .chars()
+                    .all(|c| Direction::from_char(c) == Some(direction))
+            );
+        }
+        assert_eq!(Direction::from_char('x'), None);
+        assert_eq!("L".parse::<Direction>().unwrap(), Direction::Left);
+        assert!("LR".parse::<Direction>().is_err());
+        assert_eq!("SW".parse::<Direction8>().unwrap(), Direction8::DownLeft);
+        assert_eq!("<".parse::<Direction8>().unwrap(), Direction8::Left);
+    }
+
+    #[test]
+    fn test_turning() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert_eq!(Direction8::Up.turn_right(), Direction8::UpRight);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownRight.opposite(), Direction8::UpLeft);
+        for direction in Direction::all() {
+            assert_eq!(
+                direction.turn_right().to_vec2d(),
+                direction.to_vec2d().rotate_right()
+            );
+            assert_eq!(direction.turn_left().turn_right(), direction);
+        }
+    }
+
+    #[test]
+    fn test_vectors_match_constants() {
+        assert_eq!(
+            Direction::all().map(Vec2D::from).collect::<Vec<_>>(),
+            vec![vec2d::UP, vec2d::RIGHT, vec2d::DOWN, vec2d::LEFT]
+        );
+        let mut eight = Direction8::all().map(Vec2D::from).collect::<Vec<_>>();
+        eight.sort();
+        let mut adjacent = vec2d::ADJACENT8.to_vec();
+        adjacent.sort();
+        assert_eq!(eight, adjacent);
+        assert!(
+            Direction8::all()
+                .all(|d| d.is_diagonal() == (d.to_vec2d().manhattan_distance(&vec2d::ZERO) == 2))
+        );
+    }
+}
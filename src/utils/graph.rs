@@ -0,0 +1,374 @@
+#![allow(dead_code)]
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use super::{
+    big_uint::{Count, Overflow},
+    parse::{ParseError, key_value, parse_lines},
+};
+
+/// A directed graph whose nodes are string labels interned to dense `u32` ids,
+/// with adjacency lists kept in both directions.
+#[derive(Clone, Default, Debug)]
+pub struct Graph {
+    labels: Vec<String>,
+    ids: HashMap<String, u32>,
+    successors: Vec<Vec<u32>>,
+    predecessors: Vec<Vec<u32>>,
+}
+
+/// A cycle found where the graph was expected to be acyclic, listing its
+/// nodes in order; the last one has an edge back to the first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle(pub Vec<String>);
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cycle detected: ")?;
+        for label in &self.0 {
+            write!(f, "{} -> ", label)?;
+        }
+        write!(f, "{}", self.0.first().map_or("", String::as_str))
+    }
+}
+
+impl Error for Cycle {}
+
+/// Why `count_paths` couldn't count the paths
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathCountError {
+    Cycle(Cycle),
+    /// The count doesn't fit the count type; a wider one such as `BigUint` may do
+    Overflow,
+}
+
+impl Display for PathCountError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cycle(cycle) => write!(f, "{}", cycle),
+            Self::Overflow => write!(f, "{}", Overflow),
+        }
+    }
+}
+
+impl Error for PathCountError {}
+
+impl From<Cycle> for PathCountError {
+    fn from(cycle: Cycle) -> Self {
+        Self::Cycle(cycle)
+    }
+}
+
+impl From<Overflow> for PathCountError {
+    fn from(_: Overflow) -> Self {
+        Self::Overflow
+    }
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of `label`, adding it as a new node if it isn't known yet
+    pub fn intern(&mut self, label: &str) -> u32 {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+        let id = self.labels.len() as u32;
+        self.labels.push(label.to_string());
+        self.ids.insert(label.to_string(), id);
+        self.successors.push(Vec::new());
+        self.predecessors.push(Vec::new());
+        id
+    }
+
+    pub fn id(&self, label: &str) -> Option<u32> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: u32) -> &str {
+        &self.labels[id as usize]
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = u32> + use<> {
+        0..self.labels.len() as u32
+    }
+
+    pub fn add_edge(&mut self, from: u32, to: u32) {
+        self.successors[from as usize].push(to);
+        self.predecessors[to as usize].push(from);
+    }
+
+    pub fn successors(&self, id: u32) -> &[u32] {
+        &self.successors[id as usize]
+    }
+
+    pub fn predecessors(&self, id: u32) -> &[u32] {
+        &self.predecessors[id as usize]
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.successors.iter().map(Vec::len).sum()
+    }
+
+    /// All nodes in an order where every edge points forward
+    pub fn topological_sort(&self) -> Result<Vec<u32>, Cycle> {
+        let mut order = self.postorder(self.nodes())?;
+        order.reverse();
+        Ok(order)
+    }
+
+    /// Some cycle of the graph, `None` if it is acyclic
+    pub fn find_cycle(&self) -> Option<Cycle> {
+        self.topological_sort().err()
+    }
+
+    /// Which nodes can be reached from `start`, `start` included, indexed by id
+    pub fn reachable(&self, start: u32) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        seen[start as usize] = true;
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for &next in self.successors(node) {
+                if !std::mem::replace(&mut seen[next as usize], true) {
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    pub fn can_reach(&self, from: u32, to: u32) -> bool {
+        self.reachable(from)[to as usize]
+    }
+
+    /// Number of distinct paths from `from` to `to`, counted in `C` with
+    /// overflow checks. Each node's count is computed once, so this is linear
+    /// in the part of the graph reachable from `from`, which has to be acyclic.
+    pub fn count_paths<C: Count>(&self, from: u32, to: u32) -> Result<C, PathCountError> {
+        let mut paths = vec![C::zero(); self.len()];
+        // In postorder every node comes after all of its successors
+        for node in self.postorder([from])? {
+            paths[node as usize] = if node == to {
+                C::one()
+            } else {
+                self.successors(node)
+                    .iter()
+                    .try_fold(C::zero(), |sum, &next| sum.try_add(&paths[next as usize]))?
+            };
+        }
+        Ok(paths.swap_remove(from as usize))
+    }
+
+    /// The nodes reachable from `start` with every node after all of its
+    /// successors, failing only on a cycle that `start` can reach
+    pub fn reachable_postorder(&self, start: u32) -> Result<Vec<u32>, Cycle> {
+        self.postorder([start])
+    }
+
+    /// `count_paths` by label; unknown labels have no paths except to themselves
+    pub fn count_paths_between<C: Count>(&self, from: &str, to: &str) -> Result<C, PathCountError> {
+        match (self.id(from), self.id(to)) {
+            (Some(from), Some(to)) => self.count_paths(from, to),
+            _ if from == to => Ok(C::one()),
+            _ => Ok(C::zero()),
+        }
+    }
+
+    /// Depth-first postorder of the nodes reachable from `roots`, failing on
+    /// the first cycle it runs into
+    fn postorder(&self, roots: impl IntoIterator<Item = u32>) -> Result<Vec<u32>, Cycle> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            OnStack,
+            Done,
+        }
+        let mut state = vec![State::New; self.len()];
+        let mut order = Vec::with_capacity(self.len());
+        for root in roots {
+            if state[root as usize] != State::New {
+                continue;
+            }
+            state[root as usize] = State::OnStack;
+            // Each frame is a node and the index of the next successor to visit
+            let mut stack = vec![(root, 0)];
+            while let Some((node, index)) = stack.last_mut() {
+                let node = *node;
+                let Some(&next) = self.successors(node).get(*index) else {
+                    state[node as usize] = State::Done;
+                    order.push(node);
+                    stack.pop();
+                    continue;
+                };
+                *index += 1;
+                match state[next as usize] {
+                    State::New => {
+                        state[next as usize] = State::OnStack;
+                        stack.push((next, 0));
+                    }
+                    State::OnStack => {
+                        let start = stack.iter().position(|&(n, _)| n == next).unwrap();
+                        let cycle = stack[start..]
+                            .iter()
+                            .map(|&(n, _)| self.label(n).to_string())
+                            .collect();
+                        return Err(Cycle(cycle));
+                    }
+                    State::Done => {}
+                }
+            }
+        }
+        Ok(order)
+    }
+}
+
+/// Parses adjacency lines like `aaa: bbb ccc`, one source node per line
+impl FromStr for Graph {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut graph = Graph::new();
+        parse_lines(s, |line| {
+            let (node, outputs) = key_value(line)?;
+            let node = graph.intern(node);
+            for output in outputs.split_whitespace() {
+                let output = graph.intern(output);
+                graph.add_edge(node, output);
+            }
+            Ok(())
+        })?;
+        Ok(graph)
+    }
+}
+
+/// Writes the graph back as adjacency lines, nodes without outputs left out
+impl Display for Graph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        for node in self
+            .nodes()
+            .filter(|&node| !self.successors(node).is_empty())
+        {
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            write!(f, "{}:", self.label(node))?;
+            for &next in self.successors(node) {
+                write!(f, " {}", self.label(next))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::big_uint::BigUint;
+
+    const DIAMOND: &str = "a: b c\nb: d\nc: d\nd: e";
+
+    #[test]
+    fn test_parse_and_adjacency() {
+        let graph = DIAMOND.parse::<Graph>().unwrap();
+        assert_eq!(graph.len(), 5);
+        assert_eq!(graph.edge_count(), 5);
+        let [a, d] = ["a", "d"].map(|label| graph.id(label).unwrap());
+        assert_eq!(graph.label(a), "a");
+        assert_eq!(graph.successors(a).len(), 2);
+        assert_eq!(graph.predecessors(d).len(), 2);
+        assert_eq!(graph.to_string(), DIAMOND);
+
+        let err = "a: b\nc d".parse::<Graph>().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let graph = DIAMOND.parse::<Graph>().unwrap();
+        let order = graph.topological_sort().unwrap();
+        let position = |id: u32| order.iter().position(|&n| n == id).unwrap();
+        for node in graph.nodes() {
+            for &next in graph.successors(node) {
+                assert!(position(node) < position(next));
+            }
+        }
+        assert_eq!(graph.find_cycle(), None);
+    }
+
+    #[test]
+    fn test_cycle_is_reported() {
+        let graph = "a: b\nb: c\nc: d b".parse::<Graph>().unwrap();
+        let cycle = graph.find_cycle().unwrap();
+        assert_eq!(cycle, Cycle(vec!["b".to_string(), "c".to_string()]));
+        assert_eq!(cycle.to_string(), "Cycle detected: b -> c -> b");
+        let [a, b] = ["a", "b"].map(|label| graph.id(label).unwrap());
+        assert_eq!(
+            graph.count_paths::<u64>(a, b),
+            Err(PathCountError::Cycle(cycle))
+        );
+    }
+
+    #[test]
+    fn test_reachability_and_paths() {
+        let graph = format!("{}\nx: a", DIAMOND).parse::<Graph>().unwrap();
+        let [a, e, x] = ["a", "e", "x"].map(|label| graph.id(label).unwrap());
+        assert!(graph.can_reach(a, e));
+        assert!(!graph.can_reach(e, a));
+        assert_eq!(graph.reachable(a).iter().filter(|&&r| r).count(), 5);
+        assert_eq!(graph.count_paths::<u64>(a, e), Ok(2));
+        assert_eq!(graph.count_paths::<u64>(x, e), Ok(2));
+        assert_eq!(graph.count_paths::<u64>(e, a), Ok(0));
+        assert_eq!(graph.count_paths_between::<u64>("a", "missing"), Ok(0));
+        assert_eq!(
+            graph.count_paths_between::<u64>("missing", "missing"),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_count_paths_ignores_unreachable_cycles() {
+        let graph = "a: b\nc: d\nd: c".parse::<Graph>().unwrap();
+        assert_eq!(graph.count_paths_between::<u64>("a", "b"), Ok(1));
+    }
+
+    #[test]
+    fn test_count_paths_overflow() {
+        // A ladder of 70 diamonds has 2^70 paths from top to bottom
+        let mut graph = Graph::new();
+        for i in 0..70 {
+            let [top, left, right, bottom] = [
+                format!("n{}", i),
+                format!("l{}", i),
+                format!("r{}", i),
+                format!("n{}", i + 1),
+            ]
+            .map(|label| graph.intern(&label));
+            for (from, to) in [(top, left), (top, right), (left, bottom), (right, bottom)] {
+                graph.add_edge(from, to);
+            }
+        }
+        assert_eq!(
+            graph.count_paths_between::<u64>("n0", "n70"),
+            Err(PathCountError::Overflow)
+        );
+        let paths = graph.count_paths_between::<BigUint>("n0", "n70").unwrap();
+        assert_eq!(paths.to_string(), "1180591620717411303424");
+    }
+}
@@ -0,0 +1,290 @@
+#![allow(dead_code)]
+
+use std::{
+    fmt::{Display, Formatter},
+    ops::{Index, IndexMut},
+};
+
+use super::{
+    parse::{ParseError, chars},
+    vec2d::{ADJACENT4, ADJACENT8, Vec2D},
+};
+
+/// A rectangular grid stored row by row in a flat `Vec`, indexed by `Vec2D`
+/// with `(0, 0)` in the top left corner.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Builds a grid from cells in row-major order.
+    /// Panics if their number doesn't match the dimensions.
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "Grid size mismatch");
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses a character map, one row per line, converting each character
+    /// with `cell`. Characters it rejects and ragged rows are reported with
+    /// their position.
+    pub fn parse(s: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut cells = Vec::with_capacity(s.len());
+        let mut width = None;
+        let mut height = 0;
+        for line in s.lines() {
+            let row = chars(line, &mut cell).map_err(|err| err.within(s, line))?;
+            let row_width = row.len();
+            cells.extend(row);
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(ParseError::at(
+                        s,
+                        line,
+                        format!("Expected {} cells in row, found {}", width, row_width),
+                    ));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Self {
+            cells,
+            width: width.unwrap_or_default(),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: &Vec2D) -> bool {
+        (0..self.width as i64).contains(&position.x())
+            && (0..self.height as i64).contains(&position.y())
+    }
+
+    fn index_of(&self, position: &Vec2D) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.y() as usize * self.width + position.x() as usize)
+    }
+
+    fn position_of(&self, index: usize) -> Vec2D {
+        Vec2D::new((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    pub fn get(&self, position: &Vec2D) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: &Vec2D) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// The orthogonal neighbours of `position` that lie inside the grid
+    pub fn adjacent_4(&self, position: &Vec2D) -> impl Iterator<Item = Vec2D> + '_ {
+        let position = *position;
+        ADJACENT4
+            .into_iter()
+            .map(move |d| position + d)
+            .filter(|p| self.contains(p))
+    }
+
+    /// The orthogonal and diagonal neighbours of `position` that lie inside the grid
+    pub fn adjacent_8(&self, position: &Vec2D) -> impl Iterator<Item = Vec2D> + '_ {
+        let position = *position;
+        ADJACENT8
+            .into_iter()
+            .map(move |d| position + d)
+            .filter(|p| self.contains(p))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells[x..].iter().step_by(self.width).take(self.height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All positions in row-major order
+    pub fn positions(&self) -> impl Iterator<Item = Vec2D> + use<T> {
+        let width = self.width as i64;
+        (0..self.height as i64).flat_map(move |y| (0..width).map(move |x| Vec2D::new(x, y)))
+    }
+
+    /// Cells with their positions in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2D, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.position_of(i), cell))
+    }
+
+    /// Position of the first cell (in row-major order) equal to `value`
+    pub fn find(&self, value: &T) -> Option<Vec2D>
+    where
+        T: PartialEq,
+    {
+        self.cells
+            .iter()
+            .position(|cell| cell == value)
+            .map(|i| self.position_of(i))
+    }
+
+    /// Positions of all cells equal to `value`
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Vec2D> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(position, _)| position)
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Vec2D, &T) -> U) -> Grid<U> {
+        Grid {
+            cells: self
+                .iter()
+                .map(|(position, cell)| f(position, cell))
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<Vec2D> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Vec2D) -> &T {
+        self.get(&position)
+            .unwrap_or_else(|| panic!("{} is outside the grid", position))
+    }
+}
+
+impl<T> IndexMut<Vec2D> for Grid<T> {
+    fn index_mut(&mut self, position: Vec2D) -> &mut T {
+        self.get_mut(&position)
+            .unwrap_or_else(|| panic!("{} is outside the grid", position))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("ab.\n.#c", Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Vec2D::new(1, 1)], '#');
+        assert_eq!(grid.get(&Vec2D::new(3, 0)), None);
+        assert_eq!(grid.get(&Vec2D::new(0, -1)), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Grid::parse("..\n.x", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = Grid::parse("...\n..", Some).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn test_adjacent_stays_in_bounds() {
+        let grid = grid();
+        let mut corner = grid.adjacent_8(&Vec2D::new(0, 0)).collect::<Vec<_>>();
+        corner.sort_by_key(|p| (p.y(), p.x()));
+        assert_eq!(
+            corner,
+            vec![Vec2D::new(1, 0), Vec2D::new(0, 1), Vec2D::new(1, 1)]
+        );
+        assert_eq!(grid.adjacent_4(&Vec2D::new(1, 1)).count(), 3);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b', '.'], &['.', '#', 'c']]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["a.", "b#", ".c"]
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = grid();
+        assert_eq!(grid.find(&'#'), Some(Vec2D::new(1, 1)));
+        assert_eq!(grid.find(&'z'), None);
+        assert_eq!(
+            grid.find_all(&'.').collect::<Vec<_>>(),
+            vec![Vec2D::new(2, 0), Vec2D::new(0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_display_and_map() {
+        let grid = grid();
+        assert_eq!(grid.to_string(), "ab.\n.#c");
+        let upper = grid.map(|_, c| c.to_ascii_uppercase());
+        assert_eq!(upper.to_string(), "AB.\n.#C");
+        let mut grid = grid;
+        grid[Vec2D::new(2, 0)] = '!';
+        assert_eq!(grid.row(0), &['a', 'b', '!']);
+    }
+}
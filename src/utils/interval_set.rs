@@ -0,0 +1,239 @@
+#![allow(dead_code)]
+
+use std::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+};
+
+/// A set of integers stored as sorted, disjoint inclusive ranges.
+///
+/// Overlapping and adjacent ranges are merged on insertion, so every value
+/// belongs to exactly one stored range and lookups are a binary search.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub struct IntervalSet {
+    /// `(start, end)` pairs, both inclusive, sorted and separated by gaps
+    ranges: Vec<(u64, u64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds all values of `range`, merging it with the ranges it touches.
+    /// Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<u64>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // Ranges ending right before `start` or starting right after `end` merge too
+        let first = self
+            .ranges
+            .partition_point(|&(_, e)| e.saturating_add(1) < start);
+        let last = self
+            .ranges
+            .partition_point(|&(s, _)| s <= end.saturating_add(1));
+        if first < last {
+            start = start.min(self.ranges[first].0);
+            end = end.max(self.ranges[last - 1].1);
+        }
+        self.ranges.splice(first..last, [(start, end)]);
+    }
+
+    /// O(log n) in the number of stored ranges
+    pub fn contains(&self, value: u64) -> bool {
+        let index = self.ranges.partition_point(|&(_, end)| end < value);
+        self.ranges
+            .get(index)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of disjoint ranges
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Number of values in the set, as a `u128` since the full range
+    /// `0..=u64::MAX` holds one more value than a `u64` can count
+    pub fn total_length(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|&(start, end)| (end - start) as u128 + 1)
+            .sum()
+    }
+
+    /// The smallest and largest value, `None` if the set is empty
+    pub fn bounds(&self) -> Option<RangeInclusive<u64>> {
+        Some(self.ranges.first()?.0..=self.ranges.last()?.1)
+    }
+
+    /// The merged ranges in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<u64>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// Every value in the set in increasing order
+    pub fn values(&self) -> impl Iterator<Item = u64> + '_ {
+        self.iter().flatten()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) =
+            (self.ranges.get(i), other.ranges.get(j))
+        {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            // Whichever range ends first can't overlap anything further
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    /// The values of `self` that aren't in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        match self.bounds() {
+            Some(bounds) => self.intersection(&other.complement(bounds)),
+            None => Self::new(),
+        }
+    }
+
+    /// The values within `bounds` that aren't in the set
+    pub fn complement(&self, bounds: RangeInclusive<u64>) -> Self {
+        let (low, high) = bounds.into_inner();
+        let mut complement = Self::new();
+        let mut next = Some(low);
+        for &(start, end) in &self.ranges {
+            let Some(gap_start) = next else { break };
+            if start > gap_start {
+                complement.insert(gap_start..=(start - 1).min(high));
+            }
+            next = end.checked_add(1).map(|after| after.max(gap_start));
+        }
+        if let Some(gap_start) = next {
+            complement.insert(gap_start..=high);
+        }
+        complement
+    }
+}
+
+impl FromIterator<RangeInclusive<u64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<u64>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        set.extend(ranges);
+        set
+    }
+}
+
+impl Extend<RangeInclusive<u64>> for IntervalSet {
+    fn extend<I: IntoIterator<Item = RangeInclusive<u64>>>(&mut self, ranges: I) {
+        for range in ranges {
+            self.insert(range);
+        }
+    }
+}
+
+/// Formats the ranges as `start-end`, separated by commas
+impl Display for IntervalSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, (start, end)) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}-{}", start, end)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<u64>]) -> IntervalSet {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let set = set(&[
+            10..=14,
+            3..=5,
+            16..=20,
+            12..=18,
+            6..=6,
+            RangeInclusive::new(30, 29),
+        ]);
+        assert_eq!(set.to_string(), "3-6,10-20");
+        assert_eq!(set.range_count(), 2);
+        assert_eq!(set.total_length(), 4 + 11);
+        assert_eq!(set.bounds(), Some(3..=20));
+    }
+
+    #[test]
+    fn test_total_length_of_full_range() {
+        let mut set = set(&[0..=u64::MAX]);
+        assert_eq!(set.total_length(), u64::MAX as u128 + 1);
+        set.insert(5..=10);
+        assert_eq!(set.range_count(), 1);
+        assert_eq!(set.total_length(), 1 << 64);
+    }
+
+    #[test]
+    fn test_contains() {
+        let set = set(&[3..=5, 10..=14, 16..=20]);
+        for (value, expected) in [
+            (1, false),
+            (3, true),
+            (5, true),
+            (8, false),
+            (15, false),
+            (20, true),
+            (21, false),
+        ] {
+            assert_eq!(set.contains(value), expected, "{}", value);
+        }
+        assert!(!IntervalSet::new().contains(0));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[0..=10, 20..=30]);
+        let b = set(&[5..=25]);
+        assert_eq!(a.union(&b).to_string(), "0-30");
+        assert_eq!(a.intersection(&b).to_string(), "5-10,20-25");
+        assert_eq!(a.difference(&b).to_string(), "0-4,26-30");
+        assert_eq!(b.difference(&a).to_string(), "11-19");
+        assert_eq!(a.complement(0..=40).to_string(), "11-19,31-40");
+        assert_eq!(a.complement(5..=15).to_string(), "11-15");
+        assert_eq!(
+            set(&[0..=u64::MAX]).complement(0..=u64::MAX),
+            IntervalSet::new()
+        );
+    }
+
+    #[test]
+    fn test_values() {
+        let set = set(&[1..=2, 5..=6]);
+        assert_eq!(set.values().collect::<Vec<_>>(), vec![1, 2, 5, 6]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=2, 5..=6]);
+    }
+}
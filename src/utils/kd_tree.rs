@@ -0,0 +1,297 @@
+#![allow(dead_code)]
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use super::vecn::VecN;
+
+/// A static k-d tree over integer points for nearest-neighbour queries.
+///
+/// The tree is implicit: a permutation of the point indices in which every
+/// slice has its root at the middle, splitting on axis `depth % N`. Distances
+/// are squared Euclidean and ties are broken by index, so results are fully
+/// deterministic.
+#[derive(Clone, Debug)]
+pub struct KdTree<const N: usize> {
+    points: Vec<VecN<N>>,
+    nodes: Vec<usize>,
+}
+
+impl<const N: usize> KdTree<N> {
+    pub fn new(points: &[VecN<N>]) -> Self {
+        let mut nodes = (0..points.len()).collect::<Vec<_>>();
+        build(points, &mut nodes, 0);
+        Self {
+            points: points.to_vec(),
+            nodes,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The point with index `index` in the slice the tree was built from
+    pub fn point(&self, index: usize) -> VecN<N> {
+        self.points[index]
+    }
+
+    /// Indices of the `k` points closest to `target`, nearest first
+    pub fn nearest(&self, target: &VecN<N>, k: usize) -> Vec<usize> {
+        self.nearest_with_distances(target, k)
+            .into_iter()
+            .map(|(_, index)| index)
+            .collect()
+    }
+
+    /// Indices of the points within `square_radius` (inclusive) of `target`,
+    /// nearest first
+    pub fn within(&self, target: &VecN<N>, square_radius: i64) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.search(
+            &self.nodes,
+            0,
+            target,
+            square_radius,
+            &mut |distance, index| {
+                if distance <= square_radius {
+                    found.push((distance, index));
+                }
+                square_radius
+            },
+        );
+        found.sort_unstable();
+        found.into_iter().map(|(_, index)| index).collect()
+    }
+
+    /// Every pair of points `(i, j)` with `i < j`, closest first, produced
+    /// lazily so that taking the first few pairs doesn't cost O(n²)
+    pub fn into_closest_pairs(self) -> ClosestPairs<N> {
+        ClosestPairs::new(self)
+    }
+
+    /// `(square distance, index)` of the `k` points closest to `target`
+    fn nearest_with_distances(&self, target: &VecN<N>, k: usize) -> Vec<(i64, usize)> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        self.search(&self.nodes, 0, target, i64::MAX, &mut |distance, index| {
+            best.push((distance, index));
+            if best.len() > k {
+                best.pop();
+            }
+            match best.peek() {
+                Some(&(worst, _)) if best.len() == k => worst,
+                _ => i64::MAX,
+            }
+        });
+        best.into_sorted_vec()
+    }
+
+    /// Visits the points of the subtree `nodes` that may lie within `bound`
+    /// of `target`. `visit` is given each point's distance and index and
+    /// returns the bound for the rest of the search, which is returned too.
+    fn search(
+        &self,
+        nodes: &[usize],
+        axis: usize,
+        target: &VecN<N>,
+        mut bound: i64,
+        visit: &mut impl FnMut(i64, usize) -> i64,
+    ) -> i64 {
+        if nodes.is_empty() {
+            return bound;
+        }
+        let middle = nodes.len() / 2;
+        let point = self.points[nodes[middle]];
+        bound = visit(point.square_distance_to(target), nodes[middle]);
+
+        let (left, right) = (&nodes[..middle], &nodes[middle + 1..]);
+        let offset = target[axis] - point[axis];
+        let (near, far) = if offset < 0 {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        let next_axis = (axis + 1) % N;
+        bound = self.search(near, next_axis, target, bound, visit);
+        // Points equal to the split on this axis can be on either side, so
+        // a distance of 0 to the splitting plane still has to be searched
+        if offset * offset <= bound {
+            bound = self.search(far, next_axis, target, bound, visit);
+        }
+        bound
+    }
+}
+
+/// Arranges `nodes` so that every slice has its median along `axis` in the
+/// middle, with smaller coordinates before it
+fn build<const N: usize>(points: &[VecN<N>], nodes: &mut [usize], axis: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+    let middle = nodes.len() / 2;
+    nodes.select_nth_unstable_by_key(middle, |&index| points[index][axis]);
+    let (left, right) = nodes.split_at_mut(middle);
+    build(points, left, (axis + 1) % N);
+    build(points, &mut right[1..], (axis + 1) % N);
+}
+
+/// Lazy iterator over all pairs of points in increasing distance order, see
+/// [`KdTree::into_closest_pairs`]. Pairs at the same distance come in
+/// lexicographic order of their indices.
+///
+/// Every point streams its own neighbours, fetched from the tree in batches
+/// of doubling size, and a heap merges the streams. Each pair shows up in the
+/// streams of both its points and is reported once.
+pub struct ClosestPairs<const N: usize> {
+    tree: KdTree<N>,
+    /// The nearest neighbours of each point fetched so far, as
+    /// `(square distance, index)`, and how many of them have been merged
+    neighbours: Vec<(Vec<(i64, usize)>, usize)>,
+    /// The next neighbour of each point: `(distance, low index, high index, point)`
+    heads: BinaryHeap<Reverse<(i64, usize, usize, usize)>>,
+    last: Option<(i64, usize, usize)>,
+}
+
+impl<const N: usize> ClosestPairs<N> {
+    fn new(tree: KdTree<N>) -> Self {
+        let mut pairs = Self {
+            neighbours: vec![(Vec::new(), 0); tree.len()],
+            tree,
+            heads: BinaryHeap::new(),
+            last: None,
+        };
+        for point in 0..pairs.tree.len() {
+            pairs.advance(point);
+        }
+        pairs
+    }
+
+    /// Pushes the next neighbour of `point` onto the heap, if it has any left
+    fn advance(&mut self, point: usize) {
+        let (fetched, merged) = &mut self.neighbours[point];
+        if *merged == fetched.len() {
+            let others = self.tree.len() - 1;
+            if fetched.len() == others {
+                *fetched = Vec::new();
+                return;
+            }
+            // The point itself comes first unless duplicates tie with it
+            let k = (fetched.len() * 2).max(4).min(others) + 1;
+            let target = self.tree.point(point);
+            *fetched = self.tree.nearest_with_distances(&target, k);
+            fetched.retain(|&(_, index)| index != point);
+        }
+        let (distance, other) = fetched[*merged];
+        *merged += 1;
+        self.heads.push(Reverse((
+            distance,
+            point.min(other),
+            point.max(other),
+            point,
+        )));
+    }
+}
+
+impl<const N: usize> Iterator for ClosestPairs<N> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Reverse((distance, low, high, point)) = self.heads.pop()?;
+            self.advance(point);
+            // The copy from the other point's stream has the same key, so it
+            // comes straight after this one
+            if self.last.replace((distance, low, high)) != Some((distance, low, high)) {
+                return Some((low, high));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{rng::Rng, vec2d::Vec2D, vec3d::Vec3D};
+
+    fn random_points(count: usize, seed: u64) -> Vec<Vec3D> {
+        let mut rng = Rng::new(seed);
+        (0..count)
+            .map(|_| Vec3D::new(rng.range(-50..50), rng.range(-50..50), rng.range(-50..50)))
+            .collect()
+    }
+
+    /// Indices sorted by distance to `target`, ties by index
+    fn brute_force(points: &[Vec3D], target: &Vec3D) -> Vec<usize> {
+        let mut indices = (0..points.len()).collect::<Vec<_>>();
+        indices.sort_by_key(|&i| (points[i].square_distance_to(target), i));
+        indices
+    }
+
+    #[test]
+    fn test_nearest() {
+        let points = random_points(300, 46);
+        let tree = KdTree::new(&points);
+        let mut rng = Rng::new(1);
+        for _ in 0..50 {
+            let target = Vec3D::new(rng.range(-60..60), rng.range(-60..60), rng.range(-60..60));
+            let expected = brute_force(&points, &target);
+            for k in [0, 1, 5, 300, 400] {
+                let expected = &expected[..k.min(points.len())];
+                assert_eq!(tree.nearest(&target, k), expected, "{} k={}", target, k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_within() {
+        let points = random_points(300, 47);
+        let tree = KdTree::new(&points);
+        let target = Vec3D::new(3, -7, 12);
+        for square_radius in [0, 100, 400, 2500] {
+            let expected = brute_force(&points, &target)
+                .into_iter()
+                .filter(|&i| points[i].square_distance_to(&target) <= square_radius)
+                .collect::<Vec<_>>();
+            assert_eq!(tree.within(&target, square_radius), expected);
+        }
+    }
+
+    #[test]
+    fn test_duplicate_points() {
+        let points = vec![Vec2D::new(1, 1); 5];
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.nearest(&Vec2D::new(0, 0), 3), vec![0, 1, 2]);
+        assert_eq!(tree.into_closest_pairs().count(), 10);
+    }
+
+    #[test]
+    fn test_closest_pairs_match_sorted_pairs() {
+        let points = random_points(120, 48);
+        let mut expected = Vec::new();
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                expected.push((i, j));
+            }
+        }
+        expected.sort_by_key(|&(i, j)| points[i].square_distance_to(&points[j]));
+        let pairs = KdTree::new(&points)
+            .into_closest_pairs()
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn test_closest_pairs_of_tiny_sets() {
+        assert_eq!(KdTree::<2>::new(&[]).into_closest_pairs().next(), None);
+        assert_eq!(
+            KdTree::new(&[Vec2D::new(4, 4)]).into_closest_pairs().next(),
+            None
+        );
+    }
+}
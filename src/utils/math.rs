@@ -0,0 +1,224 @@
+#![allow(dead_code)]
+
+//! Digit arithmetic and number theory on `u64`, so that days can work with
+//! numbers directly instead of round-tripping through strings.
+
+/// `10^exponent`, panicking on overflow like any other arithmetic
+pub fn pow10(exponent: u32) -> u64 {
+    10u64.pow(exponent)
+}
+
+/// Number of decimal digits, 0 having one
+pub fn digit_count(n: u64) -> u32 {
+    n.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+/// The decimal digits of `n`, most significant first
+pub fn digits(n: u64) -> impl Iterator<Item = u8> {
+    let mut divisor = pow10(digit_count(n) - 1);
+    std::iter::from_fn(move || {
+        (divisor > 0).then(|| {
+            let digit = (n / divisor % 10) as u8;
+            divisor /= 10;
+            digit
+        })
+    })
+}
+
+/// The number written by `digits`, most significant first
+pub fn from_digits(digits: impl IntoIterator<Item = u8>) -> u64 {
+    digits
+        .into_iter()
+        .fold(0, |number, digit| number * 10 + digit as u64)
+}
+
+/// The number written by `a` followed by `b`, e.g. 12 and 345 give 12345
+pub fn concat(a: u64, b: u64) -> u64 {
+    a * pow10(digit_count(b)) + b
+}
+
+/// The number that writes a 1 every `block_len` digits, `times` times, e.g.
+/// 10101 for blocks of 2 repeated 3 times. Multiplying a block by it repeats
+/// the block. `None` if it doesn't fit in a `u64`.
+pub fn repunit(block_len: u32, times: u32) -> Option<u64> {
+    let step = 10u64.checked_pow(block_len)?;
+    (1..times).try_fold(1u64, |unit, _| unit.checked_mul(step)?.checked_add(1))
+}
+
+/// `block` written `times` times in a row, padded to `block_len` digits each,
+/// e.g. 12 in blocks of 3 repeated twice gives 12012
+pub fn repeat_block(block: u64, block_len: u32, times: u32) -> Option<u64> {
+    block.checked_mul(repunit(block_len, times)?)
+}
+
+/// Whether the digits of `n` are a block of `block_len` digits repeated at
+/// least twice, like 123123 for blocks of 3
+pub fn is_repeated_block(n: u64, block_len: u32) -> bool {
+    let len = digit_count(n);
+    block_len > 0
+        && len.is_multiple_of(block_len)
+        && len / block_len >= 2
+        && repunit(block_len, len / block_len).is_some_and(|unit| n.is_multiple_of(unit))
+}
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, 0 if either is 0
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+/// `base^exponent mod modulus` by repeated squaring
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as u64
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`, `None` unless `a`
+/// and `modulus` are coprime
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+    // Extended Euclid, keeping only the coefficients of `a`
+    let (mut r, mut next_r) = (modulus as i128, (a % modulus) as i128);
+    let (mut t, mut next_t) = (0i128, 1i128);
+    while next_r != 0 {
+        let quotient = r / next_r;
+        (r, next_r) = (next_r, r - quotient * next_r);
+        (t, next_t) = (next_t, t - quotient * next_t);
+    }
+    (r == 1 || modulus == 1).then(|| t.rem_euclid(modulus as i128) as u64)
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every pair with the
+/// Chinese remainder theorem, returning the smallest solution and the modulus
+/// it repeats with (the lcm of all moduli). Moduli don't need to be coprime.
+/// `None` if the congruences contradict each other, a modulus is 0 or the
+/// combined modulus overflows a `u64`.
+pub fn crt(congruences: impl IntoIterator<Item = (u64, u64)>) -> Option<(u64, u64)> {
+    let (mut x, mut combined) = (0u64, 1u64);
+    for (residue, modulus) in congruences {
+        if modulus == 0 {
+            return None;
+        }
+        let residue = residue % modulus;
+        let g = gcd(combined, modulus);
+        let difference = (residue as i128 - x as i128).rem_euclid(modulus as i128) as u64;
+        if !difference.is_multiple_of(g) {
+            return None;
+        }
+        // x + combined * k ≡ residue (mod modulus), solved for k modulo modulus / g
+        let reduced = modulus / g;
+        let inverse = mod_inverse(combined / g % reduced, reduced)?;
+        let k = (difference / g) as u128 * inverse as u128 % reduced as u128;
+        let next = combined.checked_mul(reduced)?;
+        x = ((x as u128 + combined as u128 * k) % next as u128) as u64;
+        combined = next;
+    }
+    Some((x, combined))
+}
+
+/// `Some(r)` if `n` is the perfect square `r * r`. The floor square root
+/// itself is `u64::isqrt`.
+pub fn exact_sqrt(n: u64) -> Option<u64> {
+    let root = n.isqrt();
+    (root * root == n).then_some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(9), 1);
+        assert_eq!(digit_count(10), 2);
+        assert_eq!(digit_count(u64::MAX), 20);
+        assert_eq!(digits(90210).collect::<Vec<_>>(), vec![9, 0, 2, 1, 0]);
+        assert_eq!(digits(0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(from_digits(digits(u64::MAX)), u64::MAX);
+        assert_eq!(concat(12, 345), 12345);
+        assert_eq!(concat(7, 0), 70);
+    }
+
+    #[test]
+    fn test_repeated_blocks() {
+        assert_eq!(repunit(2, 3), Some(10101));
+        assert_eq!(repunit(5, 1), Some(1));
+        assert_eq!(repunit(10, 2), Some(10_000_000_001));
+        assert_eq!(repunit(10, 3), None);
+        assert_eq!(repeat_block(12, 3, 2), Some(12012));
+        assert_eq!(repeat_block(4, 1, 5), Some(44444));
+        assert!(is_repeated_block(123123, 3));
+        assert!(is_repeated_block(1111, 1));
+        assert!(is_repeated_block(1111, 2));
+        assert!(!is_repeated_block(1111, 4));
+        assert!(!is_repeated_block(123124, 3));
+        assert!(!is_repeated_block(12012, 3));
+        assert!(!is_repeated_block(7, 0));
+    }
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+    }
+
+    #[test]
+    fn test_modular_arithmetic() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 7), 1);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(5, 1), Some(0));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt([(0, 1 << 40), (1, (1 << 40) - 1)]), None);
+        let (x, modulus) = crt([(5, 1 << 31), (7, (1 << 31) - 1)]).unwrap();
+        assert_eq!((x % (1 << 31), x % ((1 << 31) - 1)), (5, 7));
+        assert_eq!(modulus, (1 << 31) * ((1 << 31) - 1));
+    }
+
+    #[test]
+    fn test_exact_sqrt() {
+        assert_eq!(exact_sqrt(0), Some(0));
+        assert_eq!(exact_sqrt(144), Some(12));
+        assert_eq!(exact_sqrt(145), None);
+        assert_eq!(
+            exact_sqrt(u32::MAX as u64 * u32::MAX as u64),
+            Some(u32::MAX as u64)
+        );
+    }
+}
@@ -0,0 +1,168 @@
+#![allow(dead_code)]
+
+use std::{collections::HashMap, hash::Hash};
+
+/// A memoized recursive function.
+///
+/// The function is given a handle to recurse through along with its argument,
+/// so a recursive solver can be written as plain recursion and every argument
+/// is still only computed once:
+///
+/// ```ignore
+/// let mut fib = Memo::new(|fib: &mut dyn FnMut(u64) -> u64, n: u64| {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// });
+/// assert_eq!(fib.get(90), 2880067194370816120);
+/// ```
+///
+/// Recursion goes as deep as the chain of arguments that aren't cached yet, so
+/// for very long chains it helps to call `get` on the deepest arguments first.
+pub struct Memo<K, V, F> {
+    function: F,
+    cache: Cache<K, V>,
+}
+
+/// How well a [`Memo`] has been doing
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Number of results currently cached
+    pub cached: usize,
+}
+
+struct Cache<K, V> {
+    entries: HashMap<K, V>,
+    limit: Option<usize>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K, V, F> Memo<K, V, F>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn new(function: F) -> Self {
+        Self {
+            function,
+            cache: Cache {
+                entries: HashMap::new(),
+                limit: None,
+                hits: 0,
+                misses: 0,
+            },
+        }
+    }
+
+    /// Caps the number of cached results. When the cache is full it is
+    /// emptied before the next insertion, which bounds memory at the cost of
+    /// recomputing results that are needed again.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.cache.limit = Some(limit.max(1));
+        self
+    }
+
+    /// The function's result for `key`, computed at most once while cached
+    pub fn get(&mut self, key: K) -> V {
+        self.cache.get(key, &self.function)
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.cache.hits,
+            misses: self.cache.misses,
+            cached: self.cache.entries.len(),
+        }
+    }
+
+    /// Logs the hit and miss counts at debug level, labelled with `name`
+    pub fn log_stats(&self, name: &str) {
+        let stats = self.stats();
+        let lookups = stats.hits + stats.misses;
+        log::debug!(
+            "{}: {} lookups, {} hits ({:.1}%), {} misses, {} cached",
+            name,
+            lookups,
+            stats.hits,
+            stats.hits as f64 * 100.0 / lookups.max(1) as f64,
+            stats.misses,
+            stats.cached
+        );
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Cache<K, V> {
+    fn get(&mut self, key: K, function: &impl Fn(&mut dyn FnMut(K) -> V, K) -> V) -> V {
+        if let Some(value) = self.entries.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = function(&mut |key| self.get(key, function), key.clone());
+        if self.limit.is_some_and(|limit| self.entries.len() >= limit) {
+            self.entries.clear();
+        }
+        self.entries.insert(key, value.clone());
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_recursion_is_memoized() {
+        let calls = Cell::new(0);
+        let mut fib = Memo::new(|fib: &mut dyn FnMut(u64) -> u64, n: u64| {
+            calls.set(calls.get() + 1);
+            if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+        });
+        assert_eq!(fib.get(90), 2880067194370816120);
+        assert_eq!(calls.get(), 91);
+        assert_eq!(fib.get(50), 12586269025);
+        assert_eq!(calls.get(), 91);
+        assert_eq!(
+            fib.stats(),
+            MemoStats {
+                hits: 88 + 1,
+                misses: 91,
+                cached: 91
+            }
+        );
+    }
+
+    #[test]
+    fn test_tuple_keys() {
+        // Lattice paths through a grid, keyed by position
+        let mut paths = Memo::new(|paths: &mut dyn FnMut((u32, u32)) -> u64, (x, y)| {
+            if x == 0 || y == 0 {
+                1
+            } else {
+                paths((x - 1, y)) + paths((x, y - 1))
+            }
+        });
+        assert_eq!(paths.get((16, 16)), 601080390);
+        assert_eq!(paths.stats().cached, 17 * 17 - 1);
+    }
+
+    #[test]
+    fn test_limit_bounds_the_cache() {
+        let collatz = |steps: &mut dyn FnMut(u64) -> u64, n: u64| match n {
+            1 => 0,
+            n if n % 2 == 0 => 1 + steps(n / 2),
+            n => 1 + steps(3 * n + 1),
+        };
+        let mut unlimited = Memo::new(collatz);
+        let mut limited = Memo::new(collatz).with_limit(100);
+        for n in 1..1000 {
+            assert_eq!(limited.get(n), unlimited.get(n));
+            assert!(limited.stats().cached <= 100);
+        }
+        assert_eq!(unlimited.get(27), 111);
+        assert!(limited.stats().misses > unlimited.stats().misses);
+    }
+}
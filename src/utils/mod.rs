@@ -1,2 +1,19 @@
+pub mod big_uint;
+pub mod bit_grid;
+pub mod direction;
+pub mod graph;
+pub mod grid;
+pub mod interval_set;
+pub mod kd_tree;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod polygon;
+pub mod rect;
+pub mod rng;
+pub mod simulation;
+pub mod union_find;
 pub mod vec2d;
 pub mod vec3d;
+pub mod vecn;
+pub mod viz;
@@ -0,0 +1,377 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+/// A parse failure that knows where in the input it happened.
+///
+/// Errors are created relative to whatever string the parser was handed, usually a
+/// single line. Callers that sliced that string out of a larger input re-anchor the
+/// error with [`ParseError::within`], so by the time it reaches the runner it points
+/// at the right line and column of the whole puzzle input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    /// Byte offset and length of the offending span, `None` if unknown
+    span: Option<(usize, usize)>,
+    /// 1-based line number (0 when the position is unknown)
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    /// The source line containing the error
+    pub excerpt: String,
+}
+
+impl ParseError {
+    /// Creates an error without position information.
+    pub fn new(message: impl Display) -> Self {
+        Self {
+            message: message.to_string(),
+            span: None,
+            line: 0,
+            column: 0,
+            excerpt: String::new(),
+        }
+    }
+
+    /// Creates an error pointing at `span`, which must be a slice of `source`.
+    /// Falls back to an unpositioned error if it is not.
+    pub fn at(source: &str, span: &str, message: impl Display) -> Self {
+        match offset_of(source, span) {
+            Some(offset) => Self::locate(source, offset, span.len(), message),
+            None => Self::new(message),
+        }
+    }
+
+    /// Re-anchors an error produced while parsing `inner`, a slice of `source`,
+    /// so that it points into `source` instead.
+    pub fn within(self, source: &str, inner: &str) -> Self {
+        let Some(inner_offset) = offset_of(source, inner) else {
+            return self;
+        };
+        match self.span {
+            Some((offset, length)) => {
+                Self::locate(source, inner_offset + offset, length, self.message)
+            }
+            None => Self::locate(source, inner_offset, inner.len(), self.message),
+        }
+    }
+
+    /// The error message without position information
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Renders the error the way a compiler would, e.g.
+    ///
+    /// ```text
+    /// error: invalid digit found in string
+    ///  --> input.txt:1:10
+    ///   |
+    /// 1 | 11-22,95-1x5
+    ///   |          ^^^
+    /// ```
+    pub fn render(&self, origin: &str) -> String {
+        if self.line == 0 {
+            return format!("error: {}\n --> {}", self.message, origin);
+        }
+        let gutter = " ".repeat(self.line.to_string().len());
+        let length = self.span.map_or(1, |(_, length)| length);
+        let remaining = self.excerpt.chars().count() + 1 - self.column;
+        let carets = "^".repeat(length.clamp(1, remaining.max(1)));
+        format!(
+            "error: {}\n{gutter}--> {}:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {}{}",
+            self.message,
+            origin,
+            self.line,
+            self.column,
+            self.line,
+            self.excerpt,
+            " ".repeat(self.column - 1),
+            carets,
+        )
+    }
+
+    fn locate(source: &str, offset: usize, length: usize, message: impl Display) -> Self {
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let excerpt = source[line_start..].lines().next().unwrap_or_default();
+        Self {
+            message: message.to_string(),
+            span: Some((offset, length)),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            excerpt: excerpt.to_string(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(
+                f,
+                "{} at line {}, column {}",
+                self.message, self.line, self.column
+            )
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `span` (a slice of `source`) with its `FromStr` implementation,
+/// reporting failures at the span's position.
+pub fn parse_at<T>(source: &str, span: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    span.parse::<T>()
+        .map_err(|err| ParseError::at(source, span, format!("{}: '{}'", err, span)))
+}
+
+/// Parses every line of `source` with `parse`, re-anchoring errors to their line.
+pub fn parse_lines<T>(
+    source: &str,
+    mut parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    source
+        .lines()
+        .map(|line| parse(line).map_err(|err| err.within(source, line)))
+        .collect()
+}
+
+/// Splits the input into its blank-line separated sections.
+pub fn sections(source: &str) -> impl Iterator<Item = &str> {
+    source.split("\n\n")
+}
+
+/// Splits the input at its first blank line, failing if there is none.
+pub fn section_pair(source: &str) -> Result<(&str, &str), ParseError> {
+    source.split_once("\n\n").ok_or_else(|| {
+        ParseError::at(
+            source,
+            &source[source.len()..],
+            "Missing blank line between sections",
+        )
+    })
+}
+
+/// Splits `s` at the first `separator`; `expected` describes the format in the error.
+pub fn split_pair<'a>(
+    s: &'a str,
+    separator: &str,
+    expected: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(separator)
+        .ok_or_else(|| ParseError::at(s, s, format!("Expected '{}', found '{}'", expected, s)))
+}
+
+/// Splits a `key: values` line, trimming both sides.
+pub fn key_value(line: &str) -> Result<(&str, &str), ParseError> {
+    let (key, values) = split_pair(line, ":", "key: values")?;
+    Ok((key.trim(), values.trim()))
+}
+
+/// Parses an inclusive range written as `start-end`.
+pub fn parse_range<T>(s: &str) -> Result<RangeInclusive<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let (start, end) = split_pair(s, "-", "start-end")?;
+    Ok(parse_at(s, start)?..=parse_at(s, end)?)
+}
+
+/// Parses every `separator`-delimited, trimmed part of `s` with `parse`,
+/// re-anchoring errors to their part.
+pub fn separated_by<T>(
+    s: &str,
+    separator: char,
+    mut parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    s.split(separator)
+        .map(str::trim)
+        .map(|part| parse(part).map_err(|err| err.within(s, part)))
+        .collect()
+}
+
+/// Parses `separator`-delimited values like `1,2,3`.
+pub fn separated<T>(s: &str, separator: char) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    separated_by(s, separator, |part| parse_at(part, part))
+}
+
+/// Parses whitespace separated values like `1 0  3`.
+pub fn words<T>(s: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    s.split_whitespace().map(|word| parse_at(s, word)).collect()
+}
+
+/// Checks that `s` held exactly `N` values.
+pub fn exactly<T, const N: usize>(s: &str, values: Vec<T>) -> Result<[T; N], ParseError> {
+    let found = values.len();
+    values
+        .try_into()
+        .map_err(|_| ParseError::at(s, s, format!("Expected {} values, found {}", N, found)))
+}
+
+/// The contents of `s` between the `open` and `close` delimiters, e.g. `[.##.]`.
+pub fn bracketed(s: &str, open: char, close: char) -> Result<&str, ParseError> {
+    s.strip_prefix(open)
+        .and_then(|inner| inner.strip_suffix(close))
+        .ok_or_else(|| {
+            ParseError::at(
+                s,
+                s,
+                format!("Expected '{}...{}', found '{}'", open, close, s),
+            )
+        })
+}
+
+/// Parses a comma separated list between delimiters, e.g. `(1,3)` or `{3,5,4,7}`.
+pub fn bracketed_list<T>(s: &str, open: char, close: char) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let inner = bracketed(s, open, close)?;
+    separated(inner, ',').map_err(|err| err.within(s, inner))
+}
+
+/// Converts every character of `s` with `cell`, reporting the first one it rejects.
+pub fn chars<T>(s: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Vec<T>, ParseError> {
+    s.char_indices()
+        .map(|(i, c)| {
+            cell(c).ok_or_else(|| {
+                ParseError::at(
+                    s,
+                    &s[i..i + c.len_utf8()],
+                    format!("Unexpected character: {}", c),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Byte offset of `span` within `source`, if it is actually a slice of it.
+fn offset_of(source: &str, span: &str) -> Option<usize> {
+    let start = source.as_ptr() as usize;
+    let offset = (span.as_ptr() as usize).checked_sub(start)?;
+    (offset + span.len() <= source.len()).then_some(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_points_to_line_and_column() {
+        let source = "1-2\n3-x\n5-6";
+        let span = &source[6..7];
+        let err = ParseError::at(source, span, "bad number");
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.excerpt, "3-x");
+    }
+
+    #[test]
+    fn test_within_reanchors_nested_errors() {
+        let source = "header\n\nab,cd\nef,g?";
+        let section = source.split_once("\n\n").unwrap().1;
+        let line = section.lines().nth(1).unwrap();
+        let err = ParseError::at(line, &line[3..], "bad value")
+            .within(section, line)
+            .within(source, section);
+        assert_eq!((err.line, err.column), (4, 4));
+        assert_eq!(err.excerpt, "ef,g?");
+    }
+
+    #[test]
+    fn test_within_without_span_points_at_inner() {
+        let source = "ok\nnot ok";
+        let line = source.lines().nth(1).unwrap();
+        let err = ParseError::new("broken").within(source, line);
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn test_at_with_foreign_span_has_no_position() {
+        let other = String::from("abc");
+        let err = ParseError::at("abc", &other, "nope");
+        assert_eq!(err.line, 0);
+        assert_eq!(err.to_string(), "nope");
+    }
+
+    #[test]
+    fn test_parse_lines_reports_failing_line() {
+        let err = parse_lines("1\n2\nthree", |line| parse_at::<u32>(line, line)).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+    }
+
+    #[test]
+    fn test_sections_and_pairs() {
+        let source = "a: 1\n\nb\n\nc";
+        assert_eq!(sections(source).collect::<Vec<_>>(), vec!["a: 1", "b", "c"]);
+        assert_eq!(section_pair(source).unwrap(), ("a: 1", "b\n\nc"));
+        let err = section_pair("a\nb").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(key_value(" key :  v w ").unwrap(), ("key", "v w"));
+        assert!(key_value("no colon").is_err());
+        assert_eq!(parse_range::<u64>("3-7").unwrap(), 3..=7);
+    }
+
+    #[test]
+    fn test_separated_values() {
+        assert_eq!(separated::<i32>("1, -2,3", ',').unwrap(), vec![1, -2, 3]);
+        let err = separated::<i32>("1,2,x", ',').unwrap_err();
+        assert_eq!(err.column, 5);
+        assert_eq!(words::<u8>(" 1  2 3").unwrap(), vec![1, 2, 3]);
+        assert_eq!(exactly::<_, 2>("", vec![1, 2]).unwrap(), [1, 2]);
+        assert!(exactly::<_, 3>("", vec![1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_bracketed() {
+        assert_eq!(bracketed("[.#]", '[', ']').unwrap(), ".#");
+        assert!(bracketed("(.#]", '[', ']').is_err());
+        assert_eq!(
+            bracketed_list::<u16>("{3,5,4}", '{', '}').unwrap(),
+            vec![3, 5, 4]
+        );
+        let err = bracketed_list::<u16>("(1,x)", '(', ')').unwrap_err();
+        assert_eq!(err.column, 4);
+    }
+
+    #[test]
+    fn test_chars() {
+        let bits = chars(".##", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        });
+        assert_eq!(bits.unwrap(), vec![false, true, true]);
+        let err = chars("..x.", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!(err.column, 3);
+    }
+
+    #[test]
+    fn test_render() {
+        let source = "11-22,95-1x5";
+        let err = ParseError::at(source, &source[9..], "invalid digit found in string");
+        assert_eq!(
+            err.render("input.txt"),
+            "error: invalid digit found in string\n --> input.txt:1:10\n  |\n1 | 11-22,95-1x5\n  |          ^^^"
+        );
+    }
+}
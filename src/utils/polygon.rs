@@ -0,0 +1,354 @@
+#![allow(dead_code)]
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use super::{rect::Rect, vec2d::Vec2D};
+
+/// A simple rectilinear polygon: a closed loop of axis-aligned edges between
+/// consecutive vertices that never crosses or touches itself.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Polygon {
+    vertices: Vec<Vec2D>,
+}
+
+/// Why a list of vertices doesn't describe a valid polygon. Indices refer to
+/// the vertices as given, edge `i` running from vertex `i` to the next one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PolygonError {
+    TooFewVertices(usize),
+    /// Edge `i` has length zero
+    RepeatedVertex(usize),
+    /// Edge `i` is neither horizontal nor vertical
+    NotRectilinear(usize),
+    /// Edges `i` and `j` cross, overlap or touch away from a shared vertex
+    SelfIntersecting(usize, usize),
+}
+
+impl Display for PolygonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooFewVertices(count) => {
+                write!(f, "A polygon needs at least 4 vertices, got {}", count)
+            }
+            Self::RepeatedVertex(i) => write!(f, "Vertex {} is repeated", i),
+            Self::NotRectilinear(i) => write!(f, "Edge {} is not axis-aligned", i),
+            Self::SelfIntersecting(i, j) => write!(f, "Edges {} and {} intersect", i, j),
+        }
+    }
+}
+
+impl Error for PolygonError {}
+
+impl PolygonError {
+    /// The vertex starting the offending edge (the later edge when two of them
+    /// intersect), `None` when there are too few vertices to blame one
+    pub fn vertex(&self) -> Option<usize> {
+        match *self {
+            Self::TooFewVertices(_) => None,
+            Self::RepeatedVertex(i) | Self::NotRectilinear(i) | Self::SelfIntersecting(_, i) => {
+                Some(i)
+            }
+        }
+    }
+}
+
+/// Where a point lies relative to a polygon
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+impl Polygon {
+    /// Builds the polygon that visits `vertices` in order and returns to the
+    /// first one. Repeating the first vertex at the end is allowed.
+    pub fn new(mut vertices: Vec<Vec2D>) -> Result<Self, PolygonError> {
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        if vertices.len() < 4 {
+            return Err(PolygonError::TooFewVertices(vertices.len()));
+        }
+        let polygon = Self { vertices };
+        let edges = polygon.edges().collect::<Vec<_>>();
+        for (i, edge) in edges.iter().enumerate() {
+            if edge.min() == edge.max() {
+                return Err(PolygonError::RepeatedVertex(i));
+            }
+            if edge.width() > 1 && edge.height() > 1 {
+                return Err(PolygonError::NotRectilinear(i));
+            }
+        }
+        for i in 0..edges.len() {
+            for j in i + 1..edges.len() {
+                let Some(shared) = edges[i].intersection(&edges[j]) else {
+                    continue;
+                };
+                // Neighbouring edges may only share their common vertex
+                let common = if j == i + 1 {
+                    Some(polygon.vertices[j])
+                } else if i == 0 && j == edges.len() - 1 {
+                    Some(polygon.vertices[0])
+                } else {
+                    None
+                };
+                if Some(shared.min()) != common || shared.min() != shared.max() {
+                    return Err(PolygonError::SelfIntersecting(i, j));
+                }
+            }
+        }
+        Ok(polygon)
+    }
+
+    pub fn vertices(&self) -> &[Vec2D] {
+        &self.vertices
+    }
+
+    /// Edge `i` runs from vertex `i` to vertex `i + 1`, wrapping around, as a
+    /// degenerate rectangle
+    pub fn edges(&self) -> impl Iterator<Item = Rect> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(from, to)| Rect::new(*from, *to))
+    }
+
+    pub fn bounds(&self) -> Rect {
+        Rect::bounding(self.vertices.iter().copied()).unwrap()
+    }
+
+    /// Twice the signed area from the shoelace formula, positive when the
+    /// vertices run counterclockwise with y pointing up
+    fn signed_double_area(&self) -> i64 {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(a, b)| a.x() * b.y() - b.x() * a.y())
+            .sum()
+    }
+
+    /// The enclosed area, treating vertices as points rather than tiles. Always
+    /// whole, since every edge is axis-aligned.
+    pub fn area(&self) -> u64 {
+        self.signed_double_area().unsigned_abs() / 2
+    }
+
+    /// Number of integer points on the boundary
+    pub fn boundary_points(&self) -> u64 {
+        self.edges().map(|edge| edge.area() - 1).sum()
+    }
+
+    /// Number of integer points strictly inside, by Pick's theorem
+    pub fn interior_points(&self) -> u64 {
+        self.area() + 1 - self.boundary_points() / 2
+    }
+
+    /// Number of integer points inside or on the boundary, i.e. the number of
+    /// tiles covered when every vertex is a tile
+    pub fn lattice_points(&self) -> u64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    pub fn locate(&self, point: &Vec2D) -> Location {
+        self.locate_doubled(*point * 2)
+    }
+
+    /// Whether `point` lies inside or on the boundary
+    pub fn contains(&self, point: &Vec2D) -> bool {
+        self.locate(point) != Location::Outside
+    }
+
+    /// Whether `point` lies strictly inside
+    pub fn contains_strictly(&self, point: &Vec2D) -> bool {
+        self.locate(point) == Location::Inside
+    }
+
+    /// Whether every point of `rect` lies inside or on the boundary
+    pub fn contains_rect(&self, rect: &Rect) -> bool {
+        if rect.width() > 1 && rect.height() > 1 {
+            // With no edge through the interior, the interior is either all
+            // inside or all outside, and so is its centre
+            return self.edges().all(|edge| !rect.overlaps_interior(&edge))
+                && self.locate_doubled(rect.min() + rect.max()) == Location::Inside;
+        }
+        // A segment can only leave the polygon where it passes a vertex's
+        // coordinate, so checking those points and the midpoints between them
+        // covers every part of it
+        let axis = if rect.width() > 1 { 0 } else { 1 };
+        let (start, end) = (rect.min()[axis], rect.max()[axis]);
+        let mut stops = self
+            .vertices
+            .iter()
+            .map(|vertex| vertex[axis])
+            .filter(|&c| start < c && c < end)
+            .chain([start, end])
+            .map(|c| c * 2)
+            .collect::<Vec<_>>();
+        stops.sort_unstable();
+        stops.dedup();
+        let mut point = rect.min() * 2;
+        stops
+            .iter()
+            .zip(stops.iter().skip(1))
+            .flat_map(|(&a, &b)| [a, (a + b) / 2])
+            .chain([end * 2])
+            .all(|c| {
+                point[axis] = c;
+                self.locate_doubled(point) != Location::Outside
+            })
+    }
+
+    /// Locates a point given at twice its coordinates, so that midpoints
+    /// between integer points can be tested too
+    fn locate_doubled(&self, point: Vec2D) -> Location {
+        let mut inside = false;
+        for edge in self.edges() {
+            let (a, b) = (edge.min() * 2, edge.max() * 2);
+            if Rect::new(a, b).contains(&point) {
+                return Location::Boundary;
+            }
+            // Cast a ray towards +x; the half-open range counts a vertex on the
+            // ray only for the vertical edge extending to larger y
+            if a.x() == b.x() && a.x() > point.x() && a.y() <= point.y() && point.y() < b.y() {
+                inside = !inside;
+            }
+        }
+        if inside {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x3 square with a 2x2 one below its left part
+    fn l_shape() -> Polygon {
+        Polygon::new(
+            [(0, 0), (3, 0), (3, 3), (2, 3), (2, 5), (0, 5)]
+                .map(Vec2D::from)
+                .to_vec(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validation() {
+        let square = [(0, 0), (2, 0), (2, 2), (0, 2)].map(Vec2D::from);
+        assert!(Polygon::new(square.to_vec()).is_ok());
+        let mut closed = square.to_vec();
+        closed.push(square[0]);
+        assert_eq!(Polygon::new(closed).unwrap().vertices().len(), 4);
+
+        let invalid = [
+            (
+                vec![(0, 0), (1, 0), (1, 1)],
+                PolygonError::TooFewVertices(3),
+            ),
+            (
+                vec![(0, 0), (2, 0), (2, 0), (2, 2), (0, 2)],
+                PolygonError::RepeatedVertex(1),
+            ),
+            (
+                vec![(0, 0), (2, 0), (2, 2), (1, 3), (0, 2)],
+                PolygonError::NotRectilinear(2),
+            ),
+            (
+                vec![(0, 0), (2, 0), (2, 2), (1, 2), (1, -1), (0, -1)],
+                PolygonError::SelfIntersecting(0, 3),
+            ),
+            (
+                vec![
+                    (0, 0),
+                    (3, 0),
+                    (3, 1),
+                    (2, 1),
+                    (2, 0),
+                    (1, 0),
+                    (1, 1),
+                    (0, 1),
+                ],
+                PolygonError::SelfIntersecting(0, 3),
+            ),
+            (
+                vec![(0, 0), (2, 0), (1, 0), (1, 1), (0, 1)],
+                PolygonError::SelfIntersecting(0, 1),
+            ),
+        ];
+        for (vertices, expected) in invalid {
+            let vertices = vertices.into_iter().map(Vec2D::from).collect::<Vec<_>>();
+            assert_eq!(Polygon::new(vertices), Err(expected));
+        }
+    }
+
+    #[test]
+    fn test_area_and_lattice_points() {
+        let polygon = l_shape();
+        assert_eq!(polygon.area(), 3 * 3 + 2 * 2);
+        assert_eq!(polygon.boundary_points(), 16);
+        assert_eq!(polygon.interior_points(), 6);
+        assert_eq!(polygon.lattice_points(), 4 * 4 + 3 * 2);
+        let reversed = Polygon::new(polygon.vertices().iter().rev().copied().collect()).unwrap();
+        assert_eq!(reversed.area(), polygon.area());
+    }
+
+    #[test]
+    fn test_locate() {
+        let polygon = l_shape();
+        for (point, expected) in [
+            ((1, 1), Location::Inside),
+            ((1, 4), Location::Inside),
+            ((0, 0), Location::Boundary),
+            ((2, 4), Location::Boundary),
+            ((3, 2), Location::Boundary),
+            ((3, 4), Location::Outside),
+            ((-1, 3), Location::Outside),
+            ((1, 6), Location::Outside),
+        ] {
+            assert_eq!(polygon.locate(&Vec2D::from(point)), expected, "{:?}", point);
+        }
+        assert!(polygon.contains(&Vec2D::new(2, 5)));
+        assert!(!polygon.contains_strictly(&Vec2D::new(2, 5)));
+    }
+
+    #[test]
+    fn test_contains_rect() {
+        let polygon = l_shape();
+        let rect = |a: (i64, i64), b: (i64, i64)| Rect::new(a.into(), b.into());
+        assert!(polygon.contains_rect(&rect((0, 0), (3, 3))));
+        assert!(polygon.contains_rect(&rect((0, 0), (2, 5))));
+        assert!(polygon.contains_rect(&rect((1, 1), (1, 1))));
+        assert!(polygon.contains_rect(&rect((0, 3), (3, 3))));
+        assert!(polygon.contains_rect(&rect((2, 0), (2, 5))));
+        assert!(!polygon.contains_rect(&rect((0, 0), (3, 5))));
+        assert!(!polygon.contains_rect(&rect((1, 4), (3, 4))));
+        assert!(!polygon.contains_rect(&rect((3, 4), (3, 5))));
+
+        // A U shape whose gap a rectangle could span without crossing an edge
+        let u = Polygon::new(
+            [
+                (0, 0),
+                (1, 0),
+                (1, 2),
+                (2, 2),
+                (2, 0),
+                (3, 0),
+                (3, 3),
+                (0, 3),
+            ]
+            .map(Vec2D::from)
+            .to_vec(),
+        )
+        .unwrap();
+        assert!(!u.contains_rect(&rect((0, 0), (3, 1))));
+        assert!(!u.contains_rect(&rect((0, 1), (3, 1))));
+        assert!(u.contains_rect(&rect((0, 2), (3, 3))));
+    }
+}
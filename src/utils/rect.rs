@@ -0,0 +1,247 @@
+#![allow(dead_code)]
+
+use std::fmt::{Display, Formatter};
+
+use super::{vec2d::Vec2D, vecn::VecN};
+
+/// An axis-aligned box spanning the integer points between two corners, both
+/// included. `Rect` and `Cuboid` are the 2D and 3D aliases.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct BoxN<const N: usize> {
+    min: VecN<N>,
+    max: VecN<N>,
+}
+
+pub type Rect = BoxN<2>;
+pub type Cuboid = BoxN<3>;
+
+impl<const N: usize> BoxN<N> {
+    /// The box between any two opposite corners
+    pub fn new(corner: VecN<N>, opposite: VecN<N>) -> Self {
+        Self {
+            min: corner.component_min(&opposite),
+            max: corner.component_max(&opposite),
+        }
+    }
+
+    /// The smallest box containing all `points`, `None` if there are none
+    pub fn bounding(points: impl IntoIterator<Item = VecN<N>>) -> Option<Self> {
+        points
+            .into_iter()
+            .map(|point| Self::new(point, point))
+            .reduce(|a, b| a.union_bounds(&b))
+    }
+
+    /// The corner with the smallest coordinates
+    pub fn min(&self) -> VecN<N> {
+        self.min
+    }
+
+    /// The corner with the largest coordinates
+    pub fn max(&self) -> VecN<N> {
+        self.max
+    }
+
+    /// Number of integer points along each axis
+    pub fn size(&self) -> VecN<N> {
+        self.max - self.min + VecN::from_array([1; N])
+    }
+
+    /// Number of integer points in the box, i.e. the area or volume counting
+    /// both boundaries
+    pub fn volume(&self) -> u64 {
+        self.size().components().iter().map(|&s| s as u64).product()
+    }
+
+    /// Area or volume measured between the corners as continuous coordinates,
+    /// so a box with a zero-length side has none
+    pub fn exclusive_volume(&self) -> u64 {
+        (self.max - self.min)
+            .components()
+            .iter()
+            .map(|&s| s as u64)
+            .product()
+    }
+
+    pub fn contains(&self, point: &VecN<N>) -> bool {
+        (0..N).all(|axis| (self.min[axis]..=self.max[axis]).contains(&point[axis]))
+    }
+
+    pub fn contains_box(&self, other: &Self) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    /// The points both boxes share, `None` if they are disjoint
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = self.min.component_max(&other.min);
+        let max = self.max.component_min(&other.max);
+        (0..N)
+            .all(|axis| min[axis] <= max[axis])
+            .then_some(Self { min, max })
+    }
+
+    /// The smallest box containing both boxes
+    pub fn union_bounds(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.component_min(&other.min),
+            max: self.max.component_max(&other.max),
+        }
+    }
+
+    /// Whether the open interiors of the boxes overlap, so touching along a
+    /// boundary doesn't count. A degenerate box such as a segment only
+    /// overlaps if it passes strictly through the other box's interior.
+    pub fn overlaps_interior(&self, other: &Self) -> bool {
+        (0..N).all(|axis| self.min[axis] < other.max[axis] && other.min[axis] < self.max[axis])
+    }
+
+    /// All integer points in the box, the first axis varying fastest
+    pub fn points(&self) -> impl Iterator<Item = VecN<N>> + use<N> {
+        let (min, size) = (self.min, self.size());
+        (0..self.volume()).map(move |mut index| {
+            let mut point = min;
+            for axis in 0..N {
+                let extent = size[axis] as u64;
+                point[axis] += (index % extent) as i64;
+                index /= extent;
+            }
+            point
+        })
+    }
+}
+
+impl Rect {
+    /// Number of tiles covered, both edges included
+    pub fn area(&self) -> u64 {
+        self.volume()
+    }
+
+    pub fn exclusive_area(&self) -> u64 {
+        self.exclusive_volume()
+    }
+
+    pub fn width(&self) -> i64 {
+        self.size().x()
+    }
+
+    pub fn height(&self) -> i64 {
+        self.size().y()
+    }
+
+    /// The four corners, clockwise from the top left
+    pub fn corners(&self) -> [Vec2D; 4] {
+        [
+            self.min,
+            Vec2D::new(self.max.x(), self.min.y()),
+            self.max,
+            Vec2D::new(self.min.x(), self.max.y()),
+        ]
+    }
+}
+
+impl Cuboid {
+    pub fn width(&self) -> i64 {
+        self.size().x()
+    }
+
+    pub fn height(&self) -> i64 {
+        self.size().y()
+    }
+
+    pub fn depth(&self) -> i64 {
+        self.size().z()
+    }
+
+    /// Number of unit cubes on the surface of the solid made of its points
+    pub fn surface_area(&self) -> u64 {
+        let [w, h, d] = self.size().components().map(|s| s as u64);
+        2 * (w * h + h * d + d * w)
+    }
+}
+
+impl<const N: usize> Display for BoxN<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} - {}", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::vec3d::Vec3D;
+
+    #[test]
+    fn test_normalization_and_area() {
+        let rect = Rect::new(Vec2D::new(11, 1), Vec2D::new(2, 5));
+        assert_eq!(rect, Rect::new(Vec2D::new(2, 1), Vec2D::new(11, 5)));
+        assert_eq!(rect.min(), Vec2D::new(2, 1));
+        assert_eq!(rect.area(), 50);
+        assert_eq!(rect.exclusive_area(), 36);
+        assert_eq!((rect.width(), rect.height()), (10, 5));
+
+        let line = Rect::new(Vec2D::new(0, 3), Vec2D::new(4, 3));
+        assert_eq!(line.area(), 5);
+        assert_eq!(line.exclusive_area(), 0);
+
+        let cuboid = Cuboid::new(Vec3D::new(0, 0, 0), Vec3D::new(-1, 2, 3));
+        assert_eq!(cuboid.volume(), 2 * 3 * 4);
+        assert_eq!(cuboid.exclusive_volume(), 6);
+        assert_eq!(cuboid.surface_area(), 2 * (6 + 12 + 8));
+    }
+
+    #[test]
+    fn test_contains_intersection_and_union() {
+        let a = Rect::new(Vec2D::new(0, 0), Vec2D::new(4, 4));
+        let b = Rect::new(Vec2D::new(3, 2), Vec2D::new(8, 9));
+        assert!(a.contains(&Vec2D::new(4, 0)));
+        assert!(!a.contains(&Vec2D::new(5, 0)));
+        assert_eq!(
+            a.intersection(&b),
+            Some(Rect::new(Vec2D::new(3, 2), Vec2D::new(4, 4)))
+        );
+        assert_eq!(
+            a.union_bounds(&b),
+            Rect::new(Vec2D::new(0, 0), Vec2D::new(8, 9))
+        );
+        assert!(a.union_bounds(&b).contains_box(&b));
+        let far = Rect::new(Vec2D::new(5, 5), Vec2D::new(6, 6));
+        assert_eq!(a.intersection(&far), None);
+        assert_eq!(
+            Rect::bounding([Vec2D::new(3, 1), Vec2D::new(-2, 4), Vec2D::new(0, 0)]),
+            Some(Rect::new(Vec2D::new(-2, 0), Vec2D::new(3, 4)))
+        );
+    }
+
+    #[test]
+    fn test_overlaps_interior() {
+        let rect = Rect::new(Vec2D::new(0, 0), Vec2D::new(4, 4));
+        // Sharing an edge or a corner isn't an interior overlap
+        let touching = Rect::new(Vec2D::new(4, 0), Vec2D::new(8, 4));
+        assert!(rect.intersection(&touching).is_some());
+        assert!(!rect.overlaps_interior(&touching));
+        // A segment crossing the rectangle does overlap, one along its border doesn't
+        let crossing = Rect::new(Vec2D::new(2, -1), Vec2D::new(2, 9));
+        assert!(rect.overlaps_interior(&crossing));
+        let border = Rect::new(Vec2D::new(0, -1), Vec2D::new(0, 9));
+        assert!(!rect.overlaps_interior(&border));
+        let cuboid = Cuboid::new(Vec3D::new(0, 0, 0), Vec3D::new(2, 2, 2));
+        assert!(cuboid.overlaps_interior(&Cuboid::new(Vec3D::new(1, 1, 1), Vec3D::new(5, 5, 5))));
+    }
+
+    #[test]
+    fn test_points() {
+        let rect = Rect::new(Vec2D::new(1, 1), Vec2D::new(2, 2));
+        assert_eq!(
+            rect.points().collect::<Vec<_>>(),
+            vec![
+                Vec2D::new(1, 1),
+                Vec2D::new(2, 1),
+                Vec2D::new(1, 2),
+                Vec2D::new(2, 2)
+            ]
+        );
+        let cuboid = Cuboid::new(Vec3D::new(0, 0, 0), Vec3D::new(1, 2, 3));
+        assert_eq!(cuboid.points().count() as u64, cuboid.volume());
+        assert!(cuboid.points().all(|point| cuboid.contains(&point)));
+    }
+}
@@ -0,0 +1,77 @@
+#![allow(dead_code)]
+
+use std::ops::{Bound, RangeBounds};
+
+/// Small deterministic pseudo-random number generator (SplitMix64).
+///
+/// Not suitable for anything security related, but fast, seedable and
+/// good enough to produce reproducible random puzzle inputs.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly distributed integer within the given range.
+    /// Panics if the range is empty.
+    pub fn range<R: RangeBounds<i64>>(&mut self, range: R) -> i64 {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s + 1,
+            Bound::Unbounded => i64::MIN,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e,
+            Bound::Excluded(&e) => e - 1,
+            Bound::Unbounded => i64::MAX,
+        };
+        assert!(start <= end, "Empty range {}..={}", start, end);
+        let span = (end as i128 - start as i128 + 1) as u128;
+        let offset = (self.next_u64() as u128 * span) >> 64;
+        (start as i128 + offset as i128) as i64
+    }
+
+    /// Same as [`Rng::range`] but for unsigned sizes and indices.
+    pub fn range_usize<R: RangeBounds<usize>>(&mut self, range: R) -> usize {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s as i64,
+            Bound::Excluded(&s) => s as i64 + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e as i64,
+            Bound::Excluded(&e) => e as i64 - 1,
+            Bound::Unbounded => i64::MAX,
+        };
+        self.range(start..=end) as usize
+    }
+
+    /// Returns true with the given probability (0.0 to 1.0).
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range_usize(..items.len())]
+    }
+
+    /// Fisher-Yates shuffle
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range_usize(..=i);
+            items.swap(i, j);
+        }
+    }
+}
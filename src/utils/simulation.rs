@@ -0,0 +1,276 @@
+#![allow(dead_code)]
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Drives a state forward with a step function until it settles, repeats or
+/// has taken enough steps.
+///
+/// A hook sees the initial state as step 0 and every new state after it,
+/// which is where visualization and logging attach:
+///
+/// ```ignore
+/// let outcome = Simulation::new(grid, step)
+///     .with_hook(|step, grid| log::debug!("Step {}: {}", step, grid))
+///     .run_to_fixpoint();
+/// ```
+pub struct Simulation<S, F, H> {
+    state: S,
+    step: F,
+    hook: H,
+    steps: usize,
+    limit: Option<usize>,
+    started: bool,
+}
+
+/// How a run of a [`Simulation`] ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The state after `step` steps maps to itself
+    Fixpoint { step: usize },
+    /// `steps` steps were taken without settling
+    StepLimit { steps: usize },
+    /// The state after `start + length` steps is the one after `start` steps
+    Cycle { start: usize, length: usize },
+}
+
+impl<S, F> Simulation<S, F, fn(usize, &S)>
+where
+    F: FnMut(&S) -> S,
+{
+    pub fn new(state: S, step: F) -> Self {
+        Self {
+            state,
+            step,
+            hook: |_, _| {},
+            steps: 0,
+            limit: None,
+            started: false,
+        }
+    }
+}
+
+impl<S, F, H> Simulation<S, F, H>
+where
+    F: FnMut(&S) -> S,
+    H: FnMut(usize, &S),
+{
+    /// Calls `hook` with the step index and state, for the initial state and
+    /// after every step
+    pub fn with_hook<G: FnMut(usize, &S)>(self, hook: G) -> Simulation<S, F, G> {
+        Simulation {
+            state: self.state,
+            step: self.step,
+            hook,
+            steps: self.steps,
+            limit: self.limit,
+            started: self.started,
+        }
+    }
+
+    /// Stops every run once `limit` steps have been taken in total
+    pub fn with_step_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn into_state(self) -> S {
+        self.state
+    }
+
+    /// Number of steps taken so far
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Takes up to `count` more steps
+    pub fn run_steps(&mut self, count: usize) -> Outcome {
+        self.start();
+        for _ in 0..count {
+            if self.at_limit() {
+                break;
+            }
+            let next = (self.step)(&self.state);
+            self.advance(next);
+        }
+        Outcome::StepLimit { steps: self.steps }
+    }
+
+    /// Steps until the state stops changing
+    pub fn run_to_fixpoint(&mut self) -> Outcome
+    where
+        S: PartialEq,
+    {
+        self.start();
+        while !self.at_limit() {
+            let next = (self.step)(&self.state);
+            if next == self.state {
+                return Outcome::Fixpoint { step: self.steps };
+            }
+            self.advance(next);
+        }
+        Outcome::StepLimit { steps: self.steps }
+    }
+
+    /// Steps until a state repeats, remembering every state seen by its hash.
+    /// A state that maps to itself is reported as a fixpoint and not repeated.
+    pub fn run_until_cycle(&mut self) -> Outcome
+    where
+        S: Hash + Eq + Clone,
+    {
+        self.start();
+        let mut seen = HashMap::from([(self.state.clone(), self.steps)]);
+        while !self.at_limit() {
+            let next = (self.step)(&self.state);
+            if next == self.state {
+                return Outcome::Fixpoint { step: self.steps };
+            }
+            self.advance(next);
+            if let Some(start) = seen.insert(self.state.clone(), self.steps) {
+                return Outcome::Cycle {
+                    start,
+                    length: self.steps - start,
+                };
+            }
+        }
+        Outcome::StepLimit { steps: self.steps }
+    }
+
+    fn start(&mut self) {
+        if !std::mem::replace(&mut self.started, true) {
+            (self.hook)(self.steps, &self.state);
+        }
+    }
+
+    fn advance(&mut self, next: S) {
+        self.state = next;
+        self.steps += 1;
+        (self.hook)(self.steps, &self.state);
+    }
+
+    fn at_limit(&self) -> bool {
+        self.limit.is_some_and(|limit| self.steps >= limit)
+    }
+}
+
+/// Finds where the sequence `initial, step(initial), ...` starts repeating with
+/// Brent's algorithm, which only keeps two states around instead of all of
+/// them. The sequence must eventually repeat, or this never returns.
+pub fn brent<S: Clone + PartialEq>(initial: &S, mut step: impl FnMut(&S) -> S) -> Outcome {
+    // Find the cycle length by letting the hare run ahead in doubling stretches
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare `length` steps ahead, both meet where the cycle starts
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    if length == 1 {
+        Outcome::Fixpoint { step: start }
+    } else {
+        Outcome::Cycle { start, length }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, then 3, 4, 5, 6, 7 over and over
+    fn rho(n: &u32) -> u32 {
+        if *n < 7 { n + 1 } else { 3 }
+    }
+
+    #[test]
+    fn test_run_to_fixpoint() {
+        let mut steps_seen = Vec::new();
+        let outcome = Simulation::new(100u32, |n| n / 2)
+            .with_hook(|step, &n| steps_seen.push((step, n)))
+            .run_to_fixpoint();
+        assert_eq!(outcome, Outcome::Fixpoint { step: 7 });
+        assert_eq!(steps_seen.first(), Some(&(0, 100)));
+        assert_eq!(steps_seen.last(), Some(&(7, 0)));
+        assert_eq!(steps_seen.len(), 8);
+    }
+
+    #[test]
+    fn test_step_limits() {
+        let mut simulation = Simulation::new(0u64, |n| n + 1).with_step_limit(10);
+        assert_eq!(simulation.run_steps(4), Outcome::StepLimit { steps: 4 });
+        assert_eq!(*simulation.state(), 4);
+        assert_eq!(
+            simulation.run_to_fixpoint(),
+            Outcome::StepLimit { steps: 10 }
+        );
+        assert_eq!(
+            simulation.run_until_cycle(),
+            Outcome::StepLimit { steps: 10 }
+        );
+        assert_eq!(simulation.into_state(), 10);
+    }
+
+    #[test]
+    fn test_run_until_cycle() {
+        let mut simulation = Simulation::new(0, rho);
+        assert_eq!(
+            simulation.run_until_cycle(),
+            Outcome::Cycle {
+                start: 3,
+                length: 5
+            }
+        );
+        assert_eq!(simulation.steps(), 8);
+        assert_eq!(*simulation.state(), 3);
+
+        let mut settles = Simulation::new(10u32, |n| n.saturating_sub(3));
+        assert_eq!(settles.run_until_cycle(), Outcome::Fixpoint { step: 4 });
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(
+            brent(&0, rho),
+            Outcome::Cycle {
+                start: 3,
+                length: 5
+            }
+        );
+        assert_eq!(
+            brent(&5, rho),
+            Outcome::Cycle {
+                start: 0,
+                length: 5
+            }
+        );
+        assert_eq!(
+            brent(&10u32, |n| n.saturating_sub(3)),
+            Outcome::Fixpoint { step: 4 }
+        );
+        // Squaring plus one modulo 1000 runs through a tail before it cycles
+        let outcome = brent(&1u64, |x| (x * x + 1) % 1000);
+        let mut simulation = Simulation::new(1u64, |x| (x * x + 1) % 1000);
+        assert_eq!(simulation.run_until_cycle(), outcome);
+    }
+}
@@ -0,0 +1,142 @@
+#![allow(dead_code)]
+
+/// Disjoint sets over the elements `0..len`, with path compression and
+/// union by size so that every operation is effectively constant time.
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    /// Size of the component, only meaningful for roots
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    /// Creates `len` singleton components
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The representative of the component containing `element`
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // Point everything on the way directly at the root
+        let mut current = element;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+
+    /// Merges the components of `a` and `b`, returning `false` if they
+    /// already were the same component.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the component containing `element`
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Sizes of all components, largest first
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut sizes = (0..self.len())
+            .filter(|&element| self.parent[element] == element)
+            .map(|root| self.size[root])
+            .collect::<Vec<_>>();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    /// The elements of every component, each in increasing order, with
+    /// components ordered by their smallest element
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index_of_root = vec![usize::MAX; self.len()];
+        let mut components = Vec::with_capacity(self.components);
+        for element in 0..self.len() {
+            let root = self.find(element);
+            if index_of_root[root] == usize::MAX {
+                index_of_root[root] = components.len();
+                components.push(Vec::new());
+            }
+            components[index_of_root[root]].push(element);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_and_find() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.component_count(), 6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+        assert!(sets.connected(0, 3));
+        assert!(!sets.connected(0, 4));
+        assert_eq!(sets.component_count(), 3);
+        assert_eq!(sets.component_size(2), 4);
+        assert_eq!(sets.component_size(5), 1);
+    }
+
+    #[test]
+    fn test_components() {
+        let mut sets = UnionFind::new(5);
+        sets.union(4, 1);
+        sets.union(3, 0);
+        sets.union(0, 4);
+        assert_eq!(sets.component_sizes(), vec![4, 1]);
+        assert_eq!(sets.components(), vec![vec![0, 1, 3, 4], vec![2]]);
+    }
+
+    #[test]
+    fn test_long_chain_is_compressed() {
+        let mut sets = UnionFind::new(10_000);
+        for i in 1..10_000 {
+            sets.union(i - 1, i);
+        }
+        assert_eq!(sets.component_count(), 1);
+        assert_eq!(sets.component_size(0), 10_000);
+        let root = sets.find(9_999);
+        assert!((0..10_000).all(|i| sets.parent[i] == root));
+    }
+}
@@ -1,10 +1,8 @@
 #![allow(dead_code)]
 
-use std::{
-    fmt::{Display, Formatter},
-    ops::{Add, Sub},
-    str::FromStr,
-};
+use super::vecn::VecN;
+
+pub type Vec2D = VecN<2>;
 
 pub const UP: Vec2D = Vec2D::new(0, -1);
 pub const DOWN: Vec2D = Vec2D::new(0, 1);
@@ -15,22 +13,24 @@ pub const UP_RIGHT: Vec2D = Vec2D::new(1, -1);
 pub const DOWN_LEFT: Vec2D = Vec2D::new(-1, 1);
 pub const DOWN_RIGHT: Vec2D = Vec2D::new(1, 1);
 
-pub const ZERO: Vec2D = Vec2D::new(0, 0);
+pub const ZERO: Vec2D = Vec2D::ZERO;
 
 pub const ADJACENT4: [Vec2D; 4] = [UP, DOWN, LEFT, RIGHT];
 pub const ADJACENT8: [Vec2D; 8] = [
     UP, DOWN, LEFT, RIGHT, UP_LEFT, UP_RIGHT, DOWN_LEFT, DOWN_RIGHT,
 ];
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct Vec2D {
-    pub x: i64,
-    pub y: i64,
-}
-
 impl Vec2D {
     pub const fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
+        Self::from_array([x, y])
+    }
+
+    pub const fn x(&self) -> i64 {
+        self.components()[0]
+    }
+
+    pub const fn y(&self) -> i64 {
+        self.components()[1]
     }
 
     pub fn adjacent_4(&self) -> [Vec2D; 4] {
@@ -40,42 +40,82 @@ impl Vec2D {
     pub fn adjacent_8(&self) -> [Vec2D; 8] {
         ADJACENT8.map(|d| *self + d)
     }
+
+    /// Rotates by 90° clockwise as seen on screen (y pointing down), so `UP` becomes `RIGHT`
+    pub fn rotate_right(&self) -> Self {
+        Self::new(-self.y(), self.x())
+    }
+
+    /// Rotates by 90° counter-clockwise as seen on screen, so `UP` becomes `LEFT`
+    pub fn rotate_left(&self) -> Self {
+        Self::new(self.y(), -self.x())
+    }
 }
 
-impl FromStr for Vec2D {
-    type Err = anyhow::Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s
-            .split_once(',')
-            .ok_or(anyhow::anyhow!("Invalid vector string: {}", s))?;
-        Ok(Self::new(x.parse::<i64>()?, y.parse::<i64>()?))
+impl From<(i64, i64)> for Vec2D {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
     }
 }
 
-impl Display for Vec2D {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
+impl From<Vec2D> for (i64, i64) {
+    fn from(vector: Vec2D) -> Self {
+        (vector.x(), vector.y())
     }
 }
 
-impl Add for Vec2D {
-    type Output = Self;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+    #[test]
+    fn test_arithmetic() {
+        let mut v = Vec2D::new(2, -3);
+        assert_eq!(v * 3, Vec2D::new(6, -9));
+        assert_eq!(-v, Vec2D::new(-2, 3));
+        v += RIGHT;
+        assert_eq!(v, Vec2D::new(3, -3));
+        v -= UP * 2;
+        assert_eq!(v, Vec2D::new(3, -1));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Vec2D::new(1, 2);
+        let b = Vec2D::new(-3, 5);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+    }
+
+    #[test]
+    fn test_rotation() {
+        assert_eq!(UP.rotate_right(), RIGHT);
+        assert_eq!(RIGHT.rotate_right(), DOWN);
+        assert_eq!(UP.rotate_left(), LEFT);
+        let v = Vec2D::new(3, 7);
+        assert_eq!(v.rotate_right().rotate_left(), v);
+        assert_eq!(v.rotate_right().rotate_right(), -v);
     }
-}
 
-impl Sub for Vec2D {
-    type Output = Self;
+    #[test]
+    fn test_componentwise() {
+        let a = Vec2D::new(4, -2);
+        let b = Vec2D::new(-1, 6);
+        assert_eq!(a.signum(), Vec2D::new(1, -1));
+        assert_eq!(a.abs(), Vec2D::new(4, 2));
+        assert_eq!(a.component_min(&b), Vec2D::new(-1, -2));
+        assert_eq!(a.component_max(&b), Vec2D::new(4, 6));
+    }
 
-    fn sub(self, other: Self) -> Self {
-        Self {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
+    #[test]
+    fn test_ordering_and_tuples() {
+        let mut vectors = vec![Vec2D::new(1, 0), Vec2D::new(0, 5), Vec2D::new(0, 1)];
+        vectors.sort();
+        assert_eq!(
+            vectors,
+            vec![Vec2D::new(0, 1), Vec2D::new(0, 5), Vec2D::new(1, 0)]
+        );
+        assert_eq!(Vec2D::from((3, 4)), Vec2D::new(3, 4));
+        assert_eq!(<(i64, i64)>::from(Vec2D::new(3, 4)), (3, 4));
     }
 }
@@ -1,12 +1,8 @@
 #![allow(dead_code)]
 
-use std::{
-    fmt::{Display, Formatter},
-    ops::{Add, Sub},
-    str::FromStr,
-};
+use super::vecn::VecN;
 
-use anyhow::anyhow;
+pub type Vec3D = VecN<3>;
 
 pub const UP: Vec3D = Vec3D::new(0, -1, 0);
 pub const DOWN: Vec3D = Vec3D::new(0, 1, 0);
@@ -15,78 +11,175 @@ pub const RIGHT: Vec3D = Vec3D::new(1, 0, 0);
 pub const FORWARD: Vec3D = Vec3D::new(0, 0, 1);
 pub const BACKWARD: Vec3D = Vec3D::new(0, 0, -1);
 
-pub const ZERO: Vec3D = Vec3D::new(0, 0, 0);
+pub const ZERO: Vec3D = Vec3D::ZERO;
 
 pub const ADJACENT6: [Vec3D; 6] = [UP, DOWN, LEFT, RIGHT, FORWARD, BACKWARD];
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct Vec3D {
-    pub x: i64,
-    pub y: i64,
-    pub z: i64,
-}
-
 impl Vec3D {
     pub const fn new(x: i64, y: i64, z: i64) -> Self {
-        Self { x, y, z }
+        Self::from_array([x, y, z])
+    }
+
+    pub const fn x(&self) -> i64 {
+        self.components()[0]
+    }
+
+    pub const fn y(&self) -> i64 {
+        self.components()[1]
+    }
+
+    pub const fn z(&self) -> i64 {
+        self.components()[2]
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Self::new(
+            self.y() * other.z() - self.z() * other.y(),
+            self.z() * other.x() - self.x() * other.z(),
+            self.x() * other.y() - self.y() * other.x(),
+        )
     }
 
     pub fn adjacent_6(&self) -> [Vec3D; 6] {
         ADJACENT6.map(|d| *self + d)
     }
 
-    pub fn square_distance_to(&self, other: &Self) -> i64 {
-        (self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2)
+    /// The cells sharing a face, an edge or a corner with this one
+    pub fn adjacent_26(&self) -> [Vec3D; 26] {
+        let mut adjacent = [*self; 26];
+        for (cell, direction) in adjacent.iter_mut().zip(Vec3D::all_directions()) {
+            *cell += direction;
+        }
+        adjacent
     }
 }
 
-impl Display for Vec3D {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+/// A rotation by multiples of 90° around the axes, as an integer matrix
+/// applied to column vectors.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rotation([[i64; 3]; 3]);
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    /// All 24 orientations of a cube: every signed permutation matrix with
+    /// determinant 1, starting with the identity.
+    pub fn all() -> impl Iterator<Item = Rotation> {
+        const PERMUTATIONS: [([usize; 3], i64); 6] = [
+            ([0, 1, 2], 1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([0, 2, 1], -1),
+            ([2, 1, 0], -1),
+            ([1, 0, 2], -1),
+        ];
+        PERMUTATIONS
+            .into_iter()
+            .flat_map(|(permutation, parity)| {
+                (0..8).map(move |signs: usize| {
+                    let sign = |row: usize| if signs >> row & 1 == 1 { -1 } else { 1 };
+                    let mut matrix = [[0; 3]; 3];
+                    for (row, &column) in permutation.iter().enumerate() {
+                        matrix[row][column] = sign(row);
+                    }
+                    (Rotation(matrix), parity * sign(0) * sign(1) * sign(2))
+                })
+            })
+            .filter(|(_, determinant)| *determinant == 1)
+            .map(|(rotation, _)| rotation)
     }
-}
 
-impl FromStr for Vec3D {
-    type Err = anyhow::Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(',');
-        Ok(Self::new(
-            parts
-                .next()
-                .ok_or_else(|| anyhow!("Invalid vector string: {}", s))?
-                .parse::<i64>()?,
-            parts
-                .next()
-                .ok_or_else(|| anyhow!("Invalid vector string: {}", s))?
-                .parse::<i64>()?,
-            parts
-                .next()
-                .ok_or_else(|| anyhow!("Invalid vector string: {}", s))?
-                .parse::<i64>()?,
-        ))
+    pub fn apply(&self, vector: &Vec3D) -> Vec3D {
+        let [x, y, z] = self.0.map(|row| Vec3D::from_array(row).dot(vector));
+        Vec3D::new(x, y, z)
     }
-}
 
-impl Add for Vec3D {
-    type Output = Self;
+    /// The rotation that applies `other` first and then `self`
+    pub fn compose(&self, other: &Rotation) -> Rotation {
+        let mut matrix = [[0; 3]; 3];
+        for (row, cells) in matrix.iter_mut().enumerate() {
+            for (column, cell) in cells.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| self.0[row][k] * other.0[k][column]).sum();
+            }
+        }
+        Rotation(matrix)
+    }
 
-    fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
+    /// The rotation undoing this one, which for these matrices is the transpose
+    pub fn inverse(&self) -> Rotation {
+        let mut matrix = [[0; 3]; 3];
+        for (row, cells) in matrix.iter_mut().enumerate() {
+            for (column, cell) in cells.iter_mut().enumerate() {
+                *cell = self.0[column][row];
+            }
         }
+        Rotation(matrix)
     }
 }
 
-impl Sub for Vec3D {
-    type Output = Self;
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_products() {
+        let a = Vec3D::new(1, 2, 3);
+        let b = Vec3D::new(4, 5, 6);
+        assert_eq!(a.dot(&b), 32);
+        assert_eq!(a.cross(&b), Vec3D::new(-3, 6, -3));
+        assert_eq!(RIGHT.cross(&DOWN), FORWARD);
+        assert_eq!(a.cross(&b).dot(&a), 0);
+        assert_eq!(a.manhattan_distance(&b), 9);
+        assert_eq!(-(a * 2), Vec3D::new(-2, -4, -6));
+    }
+
+    #[test]
+    fn test_adjacent_26() {
+        let center = Vec3D::new(5, 5, 5);
+        let adjacent = center.adjacent_26();
+        assert_eq!(adjacent.iter().collect::<HashSet<_>>().len(), 26);
+        assert!(
+            adjacent
+                .iter()
+                .all(|cell| cell.chebyshev_distance(&center) == 1)
+        );
+        assert!(
+            center
+                .adjacent_6()
+                .iter()
+                .all(|cell| adjacent.contains(cell))
+        );
+    }
+
+    #[test]
+    fn test_rotations() {
+        let rotations = Rotation::all().collect::<Vec<_>>();
+        assert_eq!(rotations.len(), 24);
+        assert_eq!(rotations[0], Rotation::IDENTITY);
+
+        // A point without symmetries ends up in 24 distinct places at the same distance
+        let point = Vec3D::new(1, 2, 3);
+        let images = rotations
+            .iter()
+            .map(|r| r.apply(&point))
+            .collect::<HashSet<_>>();
+        assert_eq!(images.len(), 24);
+        assert!(
+            images
+                .iter()
+                .all(|image| image.square_distance_to(&ZERO) == 14)
+        );
 
-    fn sub(self, other: Self) -> Self {
-        Self {
-            x: self.x - other.x,
-            y: self.y - other.y,
-            z: self.z - other.z,
+        for rotation in &rotations {
+            // Rotations keep handedness and form a group
+            assert_eq!(
+                rotation.apply(&RIGHT).cross(&rotation.apply(&DOWN)),
+                rotation.apply(&FORWARD)
+            );
+            assert_eq!(rotation.compose(&rotation.inverse()), Rotation::IDENTITY);
+            assert!(rotations.contains(&rotation.compose(&rotations[5])));
         }
     }
 }
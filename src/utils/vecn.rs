@@ -0,0 +1,243 @@
+#![allow(dead_code)]
+
+use std::{
+    array,
+    fmt::{Display, Formatter},
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use super::parse::{ParseError, exactly, separated};
+
+/// An integer vector with `N` components.
+///
+/// Ordered lexicographically by its components. `Vec2D` and `Vec3D` are aliases
+/// that add constructors, named accessors and dimension specific helpers.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct VecN<const N: usize>([i64; N]);
+
+impl<const N: usize> VecN<N> {
+    pub const ZERO: Self = Self([0; N]);
+
+    pub const fn from_array(components: [i64; N]) -> Self {
+        Self(components)
+    }
+
+    pub const fn components(&self) -> [i64; N] {
+        self.0
+    }
+
+    fn map(self, f: impl Fn(i64) -> i64) -> Self {
+        Self(self.0.map(f))
+    }
+
+    fn zip(self, other: Self, f: impl Fn(i64, i64) -> i64) -> Self {
+        Self(array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+
+    pub fn dot(&self, other: &Self) -> i64 {
+        self.0.iter().zip(other.0).map(|(a, b)| a * b).sum()
+    }
+
+    pub fn square_distance_to(&self, other: &Self) -> i64 {
+        (*self - *other).0.iter().map(|d| d * d).sum()
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> i64 {
+        (*self - *other).0.iter().map(|d| d.abs()).sum()
+    }
+
+    /// Distance when diagonal steps count as one, like a king on a chessboard
+    pub fn chebyshev_distance(&self, other: &Self) -> i64 {
+        (*self - *other)
+            .0
+            .iter()
+            .map(|d| d.abs())
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn signum(&self) -> Self {
+        self.map(i64::signum)
+    }
+
+    pub fn abs(&self) -> Self {
+        self.map(i64::abs)
+    }
+
+    /// Componentwise minimum, e.g. the lower corner of a bounding box
+    pub fn component_min(&self, other: &Self) -> Self {
+        self.zip(*other, i64::min)
+    }
+
+    /// Componentwise maximum, e.g. the upper corner of a bounding box
+    pub fn component_max(&self, other: &Self) -> Self {
+        self.zip(*other, i64::max)
+    }
+
+    /// The `2 * N` unit vectors along the axes
+    pub fn orthogonal_directions() -> impl Iterator<Item = Self> {
+        (0..N).flat_map(|axis| {
+            [-1, 1].map(|sign| {
+                let mut direction = Self::ZERO;
+                direction.0[axis] = sign;
+                direction
+            })
+        })
+    }
+
+    /// The `3^N - 1` directions to the cells around a cell, diagonals included
+    pub fn all_directions() -> impl Iterator<Item = Self> {
+        (0..3usize.pow(N as u32))
+            .map(|mut index| {
+                Self(array::from_fn(|_| {
+                    let component = (index % 3) as i64 - 1;
+                    index /= 3;
+                    component
+                }))
+            })
+            .filter(|direction| *direction != Self::ZERO)
+    }
+}
+
+impl<const N: usize> Default for VecN<N> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for VecN<N> {
+    fn from(components: [i64; N]) -> Self {
+        Self(components)
+    }
+}
+
+impl<const N: usize> From<VecN<N>> for [i64; N] {
+    fn from(vector: VecN<N>) -> Self {
+        vector.0
+    }
+}
+
+impl<const N: usize> Index<usize> for VecN<N> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for VecN<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut i64 {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> Display for VecN<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        for (i, component) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", component)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Parses comma separated components, e.g. `1,2,3`
+impl<const N: usize> FromStr for VecN<N> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(exactly(s, separated(s, ',')?)?))
+    }
+}
+
+impl<const N: usize> Add for VecN<N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip(other, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> Sub for VecN<N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip(other, |a, b| a - b)
+    }
+}
+
+impl<const N: usize> AddAssign for VecN<N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const N: usize> SubAssign for VecN<N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const N: usize> Mul<i64> for VecN<N> {
+    type Output = Self;
+
+    fn mul(self, factor: i64) -> Self {
+        self.map(|component| component * factor)
+    }
+}
+
+impl<const N: usize> Neg for VecN<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|component| -component)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        let v = "1,-2,3,4".parse::<VecN<4>>().unwrap();
+        assert_eq!(v, VecN::from_array([1, -2, 3, 4]));
+        assert_eq!(v.to_string(), "(1, -2, 3, 4)");
+        assert!("1,2".parse::<VecN<3>>().is_err());
+        assert!("1,2,3,4".parse::<VecN<3>>().is_err());
+        let err = "1,x".parse::<VecN<2>>().unwrap_err();
+        assert_eq!(err.column, 3);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = VecN::from_array([1, 2, 3, 4]);
+        let b = VecN::from_array([2, 0, 3, 8]);
+        assert_eq!(a.square_distance_to(&b), 1 + 4 + 16);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(a.dot(&b), 2 + 9 + 32);
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(VecN::<3>::orthogonal_directions().count(), 6);
+        assert!(
+            VecN::<3>::orthogonal_directions()
+                .all(|direction| direction.manhattan_distance(&VecN::ZERO) == 1)
+        );
+        assert_eq!(VecN::<2>::all_directions().count(), 8);
+        assert_eq!(VecN::<4>::all_directions().count(), 80);
+    }
+
+    #[test]
+    fn test_indexing() {
+        let mut v = VecN::from_array([5, 6]);
+        v[1] += 1;
+        assert_eq!(v[0], 5);
+        assert_eq!(<[i64; 2]>::from(v), [5, 7]);
+    }
+}
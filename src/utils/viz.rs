@@ -0,0 +1,491 @@
+#![allow(dead_code)]
+
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs,
+    io::{self, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+use anyhow::Result;
+
+use super::vec2d::Vec2D;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const WHITE: Color = Color(255, 255, 255);
+    pub const GRAY: Color = Color(128, 128, 128);
+    pub const RED: Color = Color(220, 50, 47);
+    pub const GREEN: Color = Color(80, 200, 80);
+    pub const BLUE: Color = Color(38, 139, 210);
+    pub const YELLOW: Color = Color(230, 200, 40);
+
+    fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// How a single cell is drawn: the glyph is used by the text renderers,
+/// the color by all of them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Style {
+    pub glyph: char,
+    pub color: Color,
+}
+
+impl Style {
+    pub const fn new(glyph: char, color: Color) -> Self {
+        Self { glyph, color }
+    }
+}
+
+/// A sparse picture of styled `Vec2D` cells, later cells drawing over earlier ones.
+///
+/// Everything inside the bounds that wasn't drawn gets the background style. The
+/// bounds default to the bounding box of the drawn cells.
+#[derive(Clone, Debug)]
+pub struct Scene {
+    cells: HashMap<Vec2D, Style>,
+    background: Style,
+    bounds: Option<(Vec2D, Vec2D)>,
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            background: Style::new('.', Color::BLACK),
+            bounds: None,
+        }
+    }
+
+    pub fn with_background(mut self, background: Style) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Fixes the (inclusive) area to draw, so frames of a sequence line up
+    pub fn with_bounds(mut self, min: Vec2D, max: Vec2D) -> Self {
+        self.bounds = Some((min, max));
+        self
+    }
+
+    pub fn set(&mut self, position: Vec2D, style: Style) {
+        self.cells.insert(position, style);
+    }
+
+    pub fn draw<'a>(&mut self, points: impl IntoIterator<Item = &'a Vec2D>, style: Style) {
+        for point in points {
+            self.set(*point, style);
+        }
+    }
+
+    /// Draws a straight line between two cells (Bresenham)
+    pub fn line(&mut self, from: Vec2D, to: Vec2D, style: Style) {
+        let (dx, dy) = ((to.x() - from.x()).abs(), -(to.y() - from.y()).abs());
+        let step = (to - from).signum();
+        let mut error = dx + dy;
+        let mut current = from;
+        loop {
+            self.set(current, style);
+            if current == to {
+                break;
+            }
+            if 2 * error >= dy {
+                error += dy;
+                current[0] += step[0];
+            }
+            if 2 * error <= dx {
+                error += dx;
+                current[1] += step[1];
+            }
+        }
+    }
+
+    /// Inclusive bounds of the scene, `None` if it is empty and unbounded
+    pub fn bounds(&self) -> Option<(Vec2D, Vec2D)> {
+        self.bounds.or_else(|| {
+            let mut positions = self.cells.keys();
+            let first = *positions.next()?;
+            Some(positions.fold((first, first), |(min, max), p| {
+                (min.component_min(p), max.component_max(p))
+            }))
+        })
+    }
+
+    fn size(&self) -> (usize, usize) {
+        match self.bounds() {
+            Some((min, max)) => (
+                (max.x() - min.x() + 1) as usize,
+                (max.y() - min.y() + 1) as usize,
+            ),
+            None => (0, 0),
+        }
+    }
+
+    /// Rows of styles covering the bounds
+    fn rows(&self) -> Vec<Vec<Style>> {
+        let Some((min, max)) = self.bounds() else {
+            return Vec::new();
+        };
+        (min.y()..=max.y())
+            .map(|y| {
+                (min.x()..=max.x())
+                    .map(|x| {
+                        *self
+                            .cells
+                            .get(&Vec2D::new(x, y))
+                            .unwrap_or(&self.background)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Plain glyphs, one line per row
+    pub fn to_text(&self) -> String {
+        self.rows()
+            .iter()
+            .map(|row| row.iter().map(|style| style.glyph).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Glyphs colored with 24-bit ANSI escape codes
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = String::new();
+        for row in self.rows() {
+            let mut current = None;
+            for style in row {
+                if current != Some(style.color) {
+                    let Color(r, g, b) = style.color;
+                    let _ = write!(ansi, "\x1b[38;2;{};{};{}m", r, g, b);
+                    current = Some(style.color);
+                }
+                ansi.push(style.glyph);
+            }
+            ansi.push_str("\x1b[0m\n");
+        }
+        ansi
+    }
+
+    /// RGB pixels with every cell drawn as a `scale` x `scale` square
+    fn pixels(&self, scale: usize) -> (usize, usize, Vec<u8>) {
+        let (width, height) = self.size();
+        let mut pixels = Vec::with_capacity(width * height * scale * scale * 3);
+        for row in self.rows() {
+            let line = row
+                .iter()
+                .flat_map(|style| {
+                    let Color(r, g, b) = style.color;
+                    [r, g, b].repeat(scale)
+                })
+                .collect::<Vec<_>>();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        (width * scale, height * scale, pixels)
+    }
+
+    /// Binary PPM (P6) image
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let (width, height, pixels) = self.pixels(scale);
+        let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        ppm.extend(pixels);
+        ppm
+    }
+
+    /// PNG image, stored without compression to avoid pulling in a deflate implementation
+    pub fn to_png(&self, scale: usize) -> Vec<u8> {
+        let (width, height, pixels) = self.pixels(scale);
+        let mut raw = Vec::with_capacity(pixels.len() + height);
+        for row in pixels.chunks(width * 3).take(height) {
+            raw.push(0); // no filter
+            raw.extend_from_slice(row);
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend((width as u32).to_be_bytes());
+        header.extend((height as u32).to_be_bytes());
+        header.extend([8, 2, 0, 0, 0]); // 8-bit RGB, no interlacing
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &header);
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    /// SVG image with one rectangle per drawn cell inside the bounds
+    pub fn to_svg(&self, scale: usize) -> String {
+        let (width, height) = self.size();
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" shape-rendering=\"crispEdges\">\n",
+            width * scale,
+            height * scale
+        );
+        let _ = writeln!(
+            svg,
+            "  <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+            self.background.color.hex()
+        );
+        if let Some((min, max)) = self.bounds() {
+            let mut cells = self
+                .cells
+                .iter()
+                .filter(|(p, _)| {
+                    (min.x()..=max.x()).contains(&p.x()) && (min.y()..=max.y()).contains(&p.y())
+                })
+                .collect::<Vec<_>>();
+            cells.sort_by_key(|(p, _)| (p.y(), p.x()));
+            for (p, style) in cells {
+                let _ = writeln!(
+                    svg,
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    (p.x() - min.x()) as usize * scale,
+                    (p.y() - min.y()) as usize * scale,
+                    scale,
+                    scale,
+                    style.color.hex()
+                );
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// How a frame sequence is output
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Animated in the terminal
+    Ansi,
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Ansi => "txt",
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+            Format::Svg => "svg",
+        }
+    }
+
+    /// Renders a scene, scaling cells so the image is roughly 800 pixels across
+    pub fn render(&self, scene: &Scene) -> Vec<u8> {
+        let (width, height) = scene.size();
+        let scale = (800 / width.max(height).max(1)).clamp(1, 20);
+        match self {
+            Format::Ansi => scene.to_ansi().into_bytes(),
+            Format::Ppm => scene.to_ppm(scale),
+            Format::Png => scene.to_png(scale),
+            Format::Svg => scene.to_svg(scale).into_bytes(),
+        }
+    }
+}
+
+/// Writes a frame sequence as `frame_0000.<ext>`, `frame_0001.<ext>`... into `dir`
+pub fn save_frames(frames: &[Scene], format: Format, dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    for (i, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!("frame_{:04}.{}", i, format.extension()));
+        fs::write(path, format.render(frame))?;
+    }
+    Ok(())
+}
+
+/// Plays a frame sequence in the terminal, redrawing in place
+pub fn play(frames: &[Scene], delay: Duration) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    for (i, frame) in frames.iter().enumerate() {
+        write!(stdout, "\x1b[H\x1b[2J{}", frame.to_ansi())?;
+        writeln!(stdout, "frame {}/{}", i + 1, frames.len())?;
+        stdout.flush()?;
+        thread::sleep(delay);
+    }
+    Ok(())
+}
+
+/// Maps points onto a grid of at most `max_side` cells per side, keeping the aspect
+/// ratio, so that sparse inputs with huge coordinates can still be drawn.
+pub fn scale_to_fit(points: &[Vec2D], max_side: i64) -> Vec<Vec2D> {
+    let (Some(min_x), Some(max_x)) = (
+        points.iter().map(|p| p.x()).min(),
+        points.iter().map(|p| p.x()).max(),
+    ) else {
+        return Vec::new();
+    };
+    let min_y = points.iter().map(|p| p.y()).min().unwrap_or_default();
+    let max_y = points.iter().map(|p| p.y()).max().unwrap_or_default();
+    let extent = (max_x - min_x).max(max_y - min_y).max(1);
+    if extent < max_side {
+        return points
+            .iter()
+            .map(|p| Vec2D::new(p.x() - min_x, p.y() - min_y))
+            .collect();
+    }
+    points
+        .iter()
+        .map(|p| {
+            Vec2D::new(
+                (p.x() - min_x) * (max_side - 1) / extent,
+                (p.y() - min_y) * (max_side - 1) / extent,
+            )
+        })
+        .collect()
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Zlib stream made of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+    let mut zlib = vec![0x78, 0x01];
+    let blocks = data.chunks(MAX_BLOCK).collect::<Vec<_>>();
+    if blocks.is_empty() {
+        zlib.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    for (i, block) in blocks.iter().enumerate() {
+        let last = i == blocks.len() - 1;
+        zlib.push(last as u8);
+        let length = block.len() as u16;
+        zlib.extend(length.to_le_bytes());
+        zlib.extend((!length).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend(adler32(data).to_be_bytes());
+    zlib
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROLL: Style = Style::new('@', Color::WHITE);
+
+    fn scene() -> Scene {
+        let mut scene = Scene::new();
+        scene.draw(&[Vec2D::new(1, 1), Vec2D::new(3, 2)], ROLL);
+        scene
+    }
+
+    #[test]
+    fn test_text_uses_bounding_box() {
+        assert_eq!(scene().to_text(), "@..\n..@");
+        let bounded = scene().with_bounds(Vec2D::new(0, 0), Vec2D::new(3, 2));
+        assert_eq!(bounded.to_text(), "....\n.@..\n...@");
+    }
+
+    #[test]
+    fn test_line() {
+        let mut scene = Scene::new();
+        scene.line(Vec2D::new(0, 0), Vec2D::new(3, 3), ROLL);
+        scene.line(Vec2D::new(3, 0), Vec2D::new(0, 0), ROLL);
+        assert_eq!(scene.to_text(), "@@@@\n.@..\n..@.\n...@");
+    }
+
+    #[test]
+    fn test_ansi() {
+        let ansi = scene().to_ansi();
+        assert!(ansi.starts_with("\x1b[38;2;255;255;255m@\x1b[38;2;0;0;0m.."));
+        assert_eq!(ansi.matches('\n').count(), 2);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let ppm = scene().to_ppm(2);
+        let header = b"P6\n6 4\n255\n";
+        assert!(ppm.starts_with(header));
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        // The roll in the top-left corner covers 2x2 pixels, followed by background
+        let pixels = &ppm[header.len()..];
+        assert_eq!(&pixels[..9], &[255, 255, 255, 255, 255, 255, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_png_structure() {
+        let png = scene().to_png(1);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 3, 0, 0, 0, 2]);
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = scene().to_svg(10);
+        assert!(svg.contains("width=\"30\" height=\"20\""));
+        assert!(
+            svg.contains("<rect x=\"20\" y=\"10\" width=\"10\" height=\"10\" fill=\"#ffffff\"/>")
+        );
+
+        // Cells outside explicit bounds are left out, like in the other renderers
+        let cropped = scene().with_bounds(Vec2D::new(2, 0), Vec2D::new(3, 2));
+        let svg = cropped.to_svg(10);
+        assert_eq!(svg.matches("<rect").count(), 2);
+        assert!(svg.contains("<rect x=\"10\" y=\"20\""));
+    }
+
+    #[test]
+    fn test_scale_to_fit() {
+        let points = [Vec2D::new(1000, 500), Vec2D::new(99000, 50000)];
+        assert_eq!(
+            scale_to_fit(&points, 100),
+            vec![Vec2D::new(0, 0), Vec2D::new(99, 50)]
+        );
+    }
+}